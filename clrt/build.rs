@@ -1,24 +1,111 @@
 ﻿use build_script_cfg::Cfg;
 
 fn main() {
-    use search_cl_tools::{find_opencl, OpenclPath};
+    use search_cl_tools::{
+        find_opencl, find_opencl_headers, find_opencl_lib, header_version, target_version,
+        ClVersion, OpenclPath,
+    };
     use std::{env, path::PathBuf};
 
     let cfg = Cfg::new("cl");
-    let Some(OpenclPath { inc, lib }) = find_opencl() else {
-        return;
+    let build_error = Cfg::new("cl_build_error");
+    let versions = ClVersion::ALL.map(|v| (v, Cfg::new(format!("cl_{}_{}", v.major, v.minor))));
+    let feature = |name: &str| env::var_os(format!("CARGO_FEATURE_{name}")).is_some();
+
+    // Prefer a complete installation, otherwise use whatever was found
+    // and fall back to the bundled Khronos headers.
+    let mut bundled = false;
+    let (inc, lib, header) = match find_opencl() {
+        Some(OpenclPath { inc, lib, version }) => (inc, Some(lib), Some(version)),
+        None => {
+            let inc = find_opencl_headers().unwrap_or_else(|| {
+                bundled = true;
+                PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("include")
+            });
+            let header = header_version(&inc);
+            (inc, find_opencl_lib(), header)
+        }
+    };
+
+    // The highest enabled version wins since cargo features are additive.
+    let target = if feature("CL_3_0") {
+        ClVersion::new(3, 0)
+    } else if feature("CL_2_0") {
+        ClVersion::new(2, 0)
+    } else if feature("CL_1_2") {
+        ClVersion::new(1, 2)
+    } else if let Some(target) = target_version() {
+        target
+    } else {
+        header.map_or(ClVersion::new(2, 1), |v| v.min(ClVersion::new(2, 1)))
+    };
+    let mut error = match header {
+        _ if target < ClVersion::new(1, 2) => Some(format!(
+            "clrt requires at least OpenCL 1.2, cannot target OpenCL {target}"
+        )),
+        Some(header) if header < target => Some(format!(
+            "OpenCL headers at {} only declare up to OpenCL {header}, cannot target OpenCL {target}, \
+             set `OPENCL_HEADERS` to newer headers",
+            inc.display(),
+        )),
+        _ => None,
     };
 
+    // Only the headers are needed when OpenCL is loaded at runtime.
+    let dynamic = feature("DYNAMIC");
+    let lib = lib.filter(|_| !dynamic);
+    let stub = !dynamic && lib.is_none() && feature("STUB");
+    if !dynamic && lib.is_none() && !stub {
+        error.get_or_insert_with(|| {
+            "OpenCL library not found, set `OPENCL_LIB` to the directory containing it, \
+             enable feature `dynamic` to load it at runtime, \
+             or enable feature `stub` to build an empty crate"
+                .to_string()
+        });
+    }
+    // Cargo shows the warnings of every path dependency, so only report unusual builds.
+    if bundled || stub || error.is_some() {
+        println!(
+            "cargo:warning=target OpenCL {target}, headers at {} (OpenCL {}), {}",
+            inc.display(),
+            header.map_or("unknown".into(), |v| v.to_string()),
+            match &lib {
+                Some(lib) => format!("ICD loader at {}", lib.display()),
+                None if dynamic => "ICD loader loaded at runtime".into(),
+                None => "ICD loader not found".into(),
+            },
+        );
+    }
+    match &lib {
+        Some(lib) => {
+            println!("cargo:rustc-link-search={}", lib.display());
+            println!("cargo:rustc-link-lib=OpenCL");
+        }
+        // Build an empty crate if explicitly asked to.
+        None if stub => return,
+        None => {}
+    }
+
     cfg.define();
-    println!("cargo:rustc-link-search={}", lib.display());
-    println!("cargo:rustc-link-lib=OpenCL");
+    if let Some(error) = error {
+        build_error.define();
+        println!("cargo:rustc-env=CLRT_BUILD_ERROR={error}");
+    }
+    // `cl_x_y` is defined for every version up to the target that the headers declare.
+    let max = header.map_or(target, |header| header.min(target));
+    for (version, cfg) in versions {
+        if version <= max {
+            cfg.define()
+        }
+    }
 
     // The bindgen::Builder is the main entry point to bindgen,
     // and lets you build up options for the resulting bindings.
-    let bindings = bindgen::Builder::default()
+    let mut builder = bindgen::Builder::default()
         // The input header we would like to generate bindings for.
         .header("wrapper.h")
         .clang_arg(format!("-I{}", inc.display()))
+        .clang_arg(format!("-DCL_TARGET_OPENCL_VERSION={}", target.target()))
         // Only generate bindings for the functions in these namespaces.
         .allowlist_item("CL.*")
         .allowlist_item("cl.*")
@@ -31,7 +118,12 @@ fn main() {
         // Use core instead of std in the generated bindings.
         .use_core()
         // Tell cargo to invalidate the built crate whenever any of the included header files changed.
-        .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
+        .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()));
+    if dynamic {
+        // Load the functions into a table at runtime instead of linking them.
+        builder = builder.dynamic_library_name("OpenCL");
+    }
+    let bindings = builder
         // Finish the builder and generate the bindings.
         .generate()
         // Unwrap the Result and panic on failure.
@@ -0,0 +1,390 @@
+use crate::{
+    bindings::{cl_mem, cl_uint, CL_MAP_READ, CL_MAP_WRITE, CL_MAP_WRITE_INVALIDATE_REGION},
+    kernel::{accepts_global_pointer, Argument},
+    node::{destruct, NodeParts},
+    ArgInfo, AsRaw, CommandQueue, Context, Error, EventNode, Kernel,
+};
+use std::{
+    alloc::Layout,
+    ffi::c_void,
+    mem::forget,
+    ops::{Deref, DerefMut},
+    ptr::{null_mut, NonNull},
+    slice::{from_raw_parts, from_raw_parts_mut},
+};
+
+/// 基于 `clCreateBuffer` 的设备内存对象。
+#[repr(transparent)]
+pub struct Buffer(cl_mem);
+
+impl Context {
+    #[inline]
+    pub fn buffer<T: Copy>(&self, len: usize) -> Buffer {
+        self.try_buffer::<T>(len).unwrap()
+    }
+
+    pub fn try_buffer<T: Copy>(&self, len: usize) -> Result<Buffer, Error> {
+        let size = Layout::array::<T>(len).unwrap().size();
+        Ok(Buffer(try_cl!(err => clCreateBuffer(
+            self.as_raw(),
+            CL_MEM_READ_WRITE as _,
+            size,
+            null_mut(),
+            &mut err
+        ))?))
+    }
+}
+
+unsafe impl Send for Buffer {}
+unsafe impl Sync for Buffer {}
+
+impl Clone for Buffer {
+    fn clone(&self) -> Self {
+        cl!(clRetainMemObject(self.0));
+        Self(self.0)
+    }
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        cl!(clReleaseMemObject(self.0))
+    }
+}
+
+impl AsRaw for Buffer {
+    type Raw = cl_mem;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0
+    }
+
+    #[inline]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn query(&self, key: cl_uint, val_size: usize, val: *mut c_void, size_ret: &mut usize) {
+        cl!(clGetMemObjectInfo(
+            self.as_raw(),
+            key,
+            val_size,
+            val,
+            size_ret
+        ))
+    }
+}
+
+impl Buffer {
+    /// 缓冲区的字节数。
+    #[inline]
+    pub fn len(&self) -> usize {
+        use crate::bindings::CL_MEM_SIZE;
+        self.query_value(CL_MEM_SIZE)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn ctx(&self) -> Context {
+        use crate::bindings::{cl_context, CL_MEM_CONTEXT};
+        let raw = self.query_value::<cl_context>(CL_MEM_CONTEXT);
+        cl!(clRetainContext(raw));
+        unsafe { Context::from_raw(raw) }
+    }
+}
+
+impl Argument for Buffer {
+    #[inline]
+    fn try_set_to(&self, kernel: &mut Kernel, index: usize) -> Result<(), Error> {
+        try_cl!(clSetKernelArg(
+            kernel.0,
+            index as _,
+            size_of::<cl_mem>(),
+            (&self.0 as *const cl_mem).cast()
+        ))
+    }
+
+    #[inline]
+    fn accepts(&self, info: &ArgInfo) -> bool {
+        accepts_global_pointer(info)
+    }
+}
+
+/// 映射到主机的缓冲区，`W` 表示是否可写。释放时自动解除映射。
+pub struct BufferMap<'a, const W: bool> {
+    queue: &'a CommandQueue,
+    buf: &'a Buffer,
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+impl<const W_: bool> Drop for BufferMap<'_, W_> {
+    fn drop(&mut self) {
+        let _ = self.unmap_(None);
+    }
+}
+
+impl<const W_: bool> BufferMap<'_, W_> {
+    /// 解除映射，与直接释放映射相同。
+    #[inline]
+    pub fn unmap(self) {
+        self.unmap_with(None)
+    }
+
+    /// 解除映射，等待 `event` 中的事件，并记录解除映射的事件。
+    pub fn unmap_with(self, event: Option<&mut EventNode>) {
+        self.unmap_(event).unwrap();
+        forget(self)
+    }
+
+    fn unmap_(&self, event: Option<&mut EventNode>) -> Result<(), Error> {
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        try_cl!(clEnqueueUnmapMemObject(
+            self.queue.as_raw(),
+            self.buf.0,
+            self.ptr.as_ptr().cast(),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+}
+
+impl<const W_: bool> Deref for BufferMap<'_, W_> {
+    type Target = [u8];
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for BufferMap<'_, true> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl CommandQueue {
+    pub fn read_buffer<T: Copy>(
+        &self,
+        dst: &mut [T],
+        src: &Buffer,
+        offset: usize,
+        event: Option<&mut EventNode>,
+    ) {
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        cl!(clEnqueueReadBuffer(
+            self.as_raw(),
+            src.0,
+            CL_FALSE,
+            offset,
+            size_of_val(dst),
+            dst.as_mut_ptr().cast(),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+
+    pub fn write_buffer<T: Copy>(
+        &self,
+        dst: &mut Buffer,
+        offset: usize,
+        src: &[T],
+        event: Option<&mut EventNode>,
+    ) {
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        cl!(clEnqueueWriteBuffer(
+            self.as_raw(),
+            dst.0,
+            CL_FALSE,
+            offset,
+            size_of_val(src),
+            src.as_ptr().cast(),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+
+    pub fn copy_buffer(
+        &self,
+        dst: &mut Buffer,
+        dst_offset: usize,
+        src: &Buffer,
+        src_offset: usize,
+        len: usize,
+        event: Option<&mut EventNode>,
+    ) {
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        cl!(clEnqueueCopyBuffer(
+            self.as_raw(),
+            src.0,
+            dst.0,
+            src_offset,
+            dst_offset,
+            len,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+
+    /// 以 `pattern` 填充 `dst` 从 `offset` 开始的 `len` 字节，二者都必须是 `pattern` 大小的整数倍。
+    pub fn fill_buffer<T: Copy>(
+        &self,
+        dst: &mut Buffer,
+        pattern: T,
+        offset: usize,
+        len: usize,
+        event: Option<&mut EventNode>,
+    ) {
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        cl!(clEnqueueFillBuffer(
+            self.as_raw(),
+            dst.0,
+            (&raw const pattern).cast(),
+            size_of::<T>(),
+            offset,
+            len,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+
+    pub fn map_buffer<'a>(
+        &'a self,
+        buf: &'a Buffer,
+        event: Option<&mut EventNode>,
+    ) -> BufferMap<'a, false> {
+        self.map_buffer_(buf, CL_MAP_READ, event)
+    }
+
+    pub fn map_buffer_mut<'a>(
+        &'a self,
+        buf: &'a mut Buffer,
+        readable: bool,
+        event: Option<&mut EventNode>,
+    ) -> BufferMap<'a, true> {
+        let flags = if readable {
+            CL_MAP_READ | CL_MAP_WRITE
+        } else {
+            CL_MAP_WRITE_INVALIDATE_REGION
+        };
+        self.map_buffer_(buf, flags, event)
+    }
+
+    fn map_buffer_<'a, const W: bool>(
+        &'a self,
+        buf: &'a Buffer,
+        flags: u32,
+        event: Option<&mut EventNode>,
+    ) -> BufferMap<'a, W> {
+        let len = buf.len();
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        let ptr = cl!(err => clEnqueueMapBuffer(
+            self.as_raw(),
+            buf.0,
+            CL_TRUE,
+            flags as _,
+            0,
+            len,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            &mut err,
+        ));
+        BufferMap {
+            queue: self,
+            buf,
+            ptr: NonNull::new(ptr.cast()).unwrap_or(NonNull::dangling()),
+            len,
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use std::ffi::CString;
+
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let n = 1 << 10;
+
+            let context = device.context();
+            let queue = context.queue();
+
+            let mut a = context.buffer::<u32>(n);
+            let mut b = context.buffer::<u32>(n);
+            let len = a.len();
+            assert_eq!(len, n * size_of::<u32>());
+
+            let host = (0..n as u32).collect::<Vec<_>>();
+            queue.write_buffer(&mut a, 0, &host, None);
+            queue.copy_buffer(&mut b, 0, &a, 0, len, None);
+
+            let mut ans = vec![0u32; n];
+            queue.read_buffer(&mut ans, &b, 0, None);
+            queue.finish();
+            assert_eq!(ans, host);
+
+            queue.fill_buffer(&mut b, 7u32, 0, len, None);
+            let mut map = queue.map_buffer_mut(&mut b, true, None);
+            {
+                let mem = unsafe {
+                    from_raw_parts_mut(map.as_mut_ptr().cast::<u32>(), map.len() / size_of::<u32>())
+                };
+                assert!(mem.iter().all(|&x| x == 7));
+                mem.fill(1);
+            }
+            map.unmap();
+            // 释放映射时自动解除映射
+            drop(queue.map_buffer(&b, None));
+
+            let program = context
+                .build_from_source(
+                    "kernel void add_one(global uint* x) { x[get_global_id(0)] += 1; }",
+                    CString::default(),
+                )
+                .unwrap();
+            let mut kernel = program.get_kernel(c"add_one").unwrap();
+            kernel.set_arg(0, &b).launch(&[0], &[n], &[1], &queue, None);
+
+            queue.read_buffer(&mut ans, &b, 0, None);
+            queue.finish();
+            assert!(ans.iter().all(|&x| x == 2));
+        }
+    }
+}
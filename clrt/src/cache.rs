@@ -0,0 +1,186 @@
+use crate::{BuildError, Context, Program};
+use std::{
+    ffi::CStr,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+};
+
+/// 程序二进制的磁盘缓存。
+///
+/// 缓存以源码、构建选项、设备名、驱动版本和平台版本为键，
+/// 命中时从保存的二进制构建程序，未命中或缓存失效时从源码构建并保存二进制。
+pub struct ProgramCache {
+    dir: PathBuf,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+const MAGIC: &[u8; 8] = b"CLRTPC01";
+
+impl ProgramCache {
+    /// 在 `dir` 目录中保存缓存。目录不存在时将在首次保存时创建。
+    #[inline]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    #[inline]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    #[inline]
+    pub fn hits(&self) -> usize {
+        self.hits.load(Relaxed)
+    }
+
+    #[inline]
+    pub fn misses(&self) -> usize {
+        self.misses.load(Relaxed)
+    }
+
+    pub fn build(
+        &self,
+        ctx: &Context,
+        source: &str,
+        options: impl AsRef<CStr>,
+    ) -> Result<Program, BuildError> {
+        let options = options.as_ref();
+        let key = key(ctx, source, options);
+        let path = self.dir.join(format!("{:016x}.bin", fnv1a(key.as_bytes())));
+
+        let count = ctx.devices().len();
+        if let Some(binaries) = fs::read(&path)
+            .ok()
+            .and_then(|data| decode(&data, &key, count))
+        {
+            // 二进制损坏或与设备不匹配时退回从源码构建
+            if let Ok(program) = ctx.build_from_binary(&binaries, options) {
+                self.hits.fetch_add(1, Relaxed);
+                return Ok(program);
+            }
+        }
+
+        self.misses.fetch_add(1, Relaxed);
+        let program = ctx.build_from_source(source, options)?;
+        // 缓存是尽力而为的，保存失败不影响构建结果
+        let _ = self.save(&path, &encode(&key, &program.binaries()));
+        Ok(program)
+    }
+
+    fn save(&self, path: &Path, data: &[u8]) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+        fs::write(&tmp, data)?;
+        fs::rename(&tmp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&tmp);
+        })
+    }
+}
+
+fn key(ctx: &Context, source: &str, options: &CStr) -> String {
+    let mut key = String::new();
+    for device in ctx.devices() {
+        writeln!(key, "device: {}", device.name()).unwrap();
+        writeln!(key, "driver: {}", device.driver_version()).unwrap();
+        writeln!(key, "platform: {}", device.platform().version()).unwrap();
+    }
+    writeln!(key, "options: {}", options.to_string_lossy()).unwrap();
+    // 保存完整源码，避免哈希冲突时加载错误的程序
+    write!(key, "source:\n{source}").unwrap();
+    key
+}
+
+/// 64 位 FNV-1a 哈希，结果与平台和编译器版本无关。
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn encode(key: &str, binaries: &[Vec<u8>]) -> Vec<u8> {
+    let mut data = MAGIC.to_vec();
+    let mut push = |bytes: &[u8]| {
+        data.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+        data.extend_from_slice(bytes);
+    };
+    push(key.as_bytes());
+    for binary in binaries {
+        push(binary)
+    }
+    data
+}
+
+/// 解码缓存，键不同或二进制数量不是 `count` 时返回 `None`。
+fn decode(data: &[u8], key: &str, count: usize) -> Option<Vec<Vec<u8>>> {
+    fn take<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+        let (len, rest) = data.split_first_chunk::<8>()?;
+        let len = usize::try_from(u64::from_le_bytes(*len)).ok()?;
+        if rest.len() < len {
+            return None;
+        }
+        let (ans, rest) = rest.split_at(len);
+        *data = rest;
+        Some(ans)
+    }
+
+    let mut data = data.strip_prefix(MAGIC)?;
+    if take(&mut data)? != key.as_bytes() {
+        return None;
+    }
+    let mut binaries = Vec::new();
+    while !data.is_empty() {
+        binaries.push(take(&mut data)?.to_vec())
+    }
+    (binaries.len() == count).then_some(binaries)
+}
+
+#[test]
+fn test_encode() {
+    let binaries = vec![vec![1, 2, 3], vec![], vec![4; 100]];
+    let data = encode("key", &binaries);
+    assert_eq!(decode(&data, "key", 3), Some(binaries));
+    assert_eq!(decode(&data, "key", 2), None);
+    assert_eq!(decode(&data, "other key", 3), None);
+    assert_eq!(decode(&data[..data.len() - 1], "key", 3), None);
+    assert_eq!(decode(b"garbage", "key", 3), None);
+    assert_eq!(fnv1a(b"clrt"), fnv1a(b"clrt"));
+    assert_ne!(fnv1a(b"clrt"), fnv1a(b"clrs"));
+}
+
+#[test]
+fn test() {
+    const PROGRAM_SOURCE: &str =
+        "kernel void add(global int* x, int a) { x[get_global_id(0)] += a; }";
+
+    let dir = std::env::temp_dir().join(format!("clrt-program-cache-{}", std::process::id()));
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let _ = fs::remove_dir_all(&dir);
+            let cache = ProgramCache::new(&dir);
+            let context = device.context();
+
+            let _ = cache.build(&context, PROGRAM_SOURCE, c"").unwrap();
+            assert_eq!((cache.hits(), cache.misses()), (0, 1));
+            let program = cache.build(&context, PROGRAM_SOURCE, c"").unwrap();
+            assert_eq!((cache.hits(), cache.misses()), (1, 1));
+            assert!(program.get_kernel(c"add").is_some());
+
+            // 损坏的缓存退回从源码构建
+            for entry in fs::read_dir(&dir).unwrap() {
+                fs::write(entry.unwrap().path(), b"corrupt").unwrap()
+            }
+            let _ = cache.build(&context, PROGRAM_SOURCE, c"").unwrap();
+            assert_eq!((cache.hits(), cache.misses()), (1, 2));
+            let _ = cache.build(&context, PROGRAM_SOURCE, c"").unwrap();
+            assert_eq!((cache.hits(), cache.misses()), (2, 2));
+        }
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -1,27 +1,245 @@
 ﻿use crate::{
-    bindings::{cl_command_queue, cl_event},
-    AsRaw, Context, Event, SvmCapabilities,
+    bindings::{
+        cl_command_queue, cl_command_queue_properties, cl_event, cl_queue_priority_khr,
+        cl_queue_throttle_khr, CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE, CL_QUEUE_PROFILING_ENABLE,
+    },
+    AsRaw, Context, Device, Error, Event,
 };
 use std::ptr::null_mut;
 
 pub struct CommandQueue {
     raw: cl_command_queue,
-    svm: SvmCapabilities,
+    #[cfg(cl_2_0)]
+    svm: crate::SvmCapabilities,
+    props: QueueProperties,
+}
+
+cl_enum! {
+    /// 队列优先级提示，需要设备支持 `cl_khr_priority_hints`。
+    pub enum QueuePriority: cl_queue_priority_khr {
+        High   = CL_QUEUE_PRIORITY_HIGH_KHR,
+        Medium = CL_QUEUE_PRIORITY_MED_KHR,
+        Low    = CL_QUEUE_PRIORITY_LOW_KHR,
+    }
+}
+
+cl_enum! {
+    /// 队列节流提示，需要设备支持 `cl_khr_throttle_hints`。
+    pub enum QueueThrottle: cl_queue_throttle_khr {
+        High   = CL_QUEUE_THROTTLE_HIGH_KHR,
+        Medium = CL_QUEUE_THROTTLE_MED_KHR,
+        Low    = CL_QUEUE_THROTTLE_LOW_KHR,
+    }
+}
+
+/// 创建命令队列时使用的属性。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct QueueProperties {
+    pub profiling: bool,
+    pub out_of_order: bool,
+    /// 设备端队列，隐含乱序执行。
+    pub on_device: bool,
+    /// 作为设备的默认设备端队列，隐含 `on_device`。
+    pub on_device_default: bool,
+    /// 设备端队列的大小（字节），`None` 时由驱动选择。
+    pub size: Option<u32>,
+    pub priority: Option<QueuePriority>,
+    pub throttle: Option<QueueThrottle>,
+}
+
+/// 命令队列的构建器，由 [`Context::queue_builder`] 创建。
+pub struct QueueBuilder<'a> {
+    ctx: &'a Context,
+    device: Option<&'a Device>,
+    props: QueueProperties,
 }
 
 impl Context {
     #[inline]
     pub fn queue(&self) -> CommandQueue {
-        let [device] = self.devices() else {
-            panic!("multi-device context is not supported")
+        self.try_queue().unwrap()
+    }
+
+    #[inline]
+    pub fn try_queue(&self) -> Result<CommandQueue, Error> {
+        self.queue_builder().try_build()
+    }
+
+    /// 在上下文的每个设备上创建一个队列，顺序与 [`Context::devices`] 一致。
+    #[inline]
+    pub fn queues(&self) -> Vec<CommandQueue> {
+        self.try_queues().unwrap()
+    }
+
+    pub fn try_queues(&self) -> Result<Vec<CommandQueue>, Error> {
+        self.devices()
+            .iter()
+            .map(|d| self.queue_builder().device(d).try_build())
+            .collect()
+    }
+
+    #[inline]
+    pub fn queue_builder(&self) -> QueueBuilder<'_> {
+        QueueBuilder {
+            ctx: self,
+            device: None,
+            props: QueueProperties::default(),
+        }
+    }
+}
+
+impl<'a> QueueBuilder<'a> {
+    /// 指定队列所在的设备，必须是上下文中的设备。不指定时使用上下文的第一个设备。
+    #[inline]
+    pub fn device(mut self, device: &'a Device) -> Self {
+        self.device = Some(device);
+        self
+    }
+
+    #[inline]
+    pub fn profiling(mut self, enable: bool) -> Self {
+        self.props.profiling = enable;
+        self
+    }
+
+    #[inline]
+    pub fn out_of_order(mut self, enable: bool) -> Self {
+        self.props.out_of_order = enable;
+        self
+    }
+
+    /// 创建设备端队列，`default` 指定是否作为设备的默认设备端队列。
+    #[inline]
+    pub fn on_device(mut self, default: bool) -> Self {
+        self.props.out_of_order = true;
+        self.props.on_device = true;
+        self.props.on_device_default = default;
+        self
+    }
+
+    /// 设备端队列的大小，不能超过 `CL_DEVICE_QUEUE_ON_DEVICE_MAX_SIZE`。
+    #[inline]
+    pub fn size(mut self, size: u32) -> Self {
+        self.props.size = Some(size);
+        self
+    }
+
+    /// 设置优先级提示。设备不支持 `cl_khr_priority_hints` 或 OpenCL™ 2.0 时忽略。
+    #[inline]
+    pub fn priority(mut self, priority: QueuePriority) -> Self {
+        self.props.priority = Some(priority);
+        self
+    }
+
+    /// 设置节流提示。设备不支持 `cl_khr_throttle_hints` 或 OpenCL™ 2.0 时忽略。
+    #[inline]
+    pub fn throttle(mut self, throttle: QueueThrottle) -> Self {
+        self.props.throttle = Some(throttle);
+        self
+    }
+
+    #[inline]
+    pub fn build(self) -> CommandQueue {
+        self.try_build().unwrap()
+    }
+
+    /// 创建队列。设备端队列和队列大小需要 OpenCL™ 2.0，不支持时返回 [`Error::Unsupported`]。
+    pub fn try_build(self) -> Result<CommandQueue, Error> {
+        let device = match self.device {
+            Some(device) => device,
+            None => &self.ctx.devices()[0],
         };
-        CommandQueue {
-            raw: cl!(err => clCreateCommandQueue(self.as_raw(), device.as_raw(), 0, &mut err)),
-            svm: device.svm_capabilities(),
+        let with_properties = cfg!(cl_2_0)
+            && device
+                .require(2, 0, "clCreateCommandQueueWithProperties")
+                .is_ok();
+
+        let mut props = self.props;
+        if !with_properties || !device.has_extension("cl_khr_priority_hints") {
+            props.priority = None
         }
+        if !with_properties || !device.has_extension("cl_khr_throttle_hints") {
+            props.throttle = None
+        }
+        if !with_properties && (props.on_device || props.size.is_some()) {
+            return Err(Error::Unsupported("clCreateCommandQueueWithProperties"));
+        }
+
+        let flags = [
+            (props.profiling, CL_QUEUE_PROFILING_ENABLE),
+            (props.out_of_order, CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE),
+        ]
+        .into_iter()
+        .filter(|(enable, _)| *enable)
+        .fold(0, |acc, (_, flag)| {
+            acc | flag as cl_command_queue_properties
+        });
+
+        let raw = match () {
+            #[cfg(cl_2_0)]
+            () if with_properties => create_with_properties(self.ctx, device, flags, &props)?,
+            () => try_cl!(err => clCreateCommandQueue(
+                self.ctx.as_raw(),
+                device.as_raw(),
+                flags,
+                &mut err
+            ))?,
+        };
+        Ok(CommandQueue {
+            raw,
+            #[cfg(cl_2_0)]
+            svm: device.svm_capabilities(),
+            props,
+        })
     }
 }
 
+#[cfg(cl_2_0)]
+fn create_with_properties(
+    ctx: &Context,
+    device: &Device,
+    flags: cl_command_queue_properties,
+    props: &QueueProperties,
+) -> Result<cl_command_queue, Error> {
+    use crate::bindings::{
+        cl_queue_properties, CL_QUEUE_ON_DEVICE, CL_QUEUE_ON_DEVICE_DEFAULT, CL_QUEUE_PRIORITY_KHR,
+        CL_QUEUE_PROPERTIES, CL_QUEUE_SIZE, CL_QUEUE_THROTTLE_KHR,
+    };
+
+    let flags = [
+        (props.on_device, CL_QUEUE_ON_DEVICE),
+        (props.on_device_default, CL_QUEUE_ON_DEVICE_DEFAULT),
+    ]
+    .into_iter()
+    .filter(|(enable, _)| *enable)
+    .fold(flags, |acc, (_, flag)| acc | flag as cl_queue_properties);
+
+    let mut list: Vec<cl_queue_properties> = vec![CL_QUEUE_PROPERTIES as _, flags];
+    if let Some(size) = props.size {
+        list.extend([CL_QUEUE_SIZE as cl_queue_properties, size as _])
+    }
+    if let Some(priority) = props.priority {
+        list.extend([
+            CL_QUEUE_PRIORITY_KHR as cl_queue_properties,
+            priority.as_raw() as _,
+        ])
+    }
+    if let Some(throttle) = props.throttle {
+        list.extend([
+            CL_QUEUE_THROTTLE_KHR as cl_queue_properties,
+            throttle.as_raw() as _,
+        ])
+    }
+    list.push(0);
+
+    try_cl!(err => clCreateCommandQueueWithProperties(
+        ctx.as_raw(),
+        device.as_raw(),
+        list.as_ptr(),
+        &mut err
+    ))
+}
+
 unsafe impl Send for CommandQueue {}
 unsafe impl Sync for CommandQueue {}
 
@@ -55,6 +273,21 @@ impl CommandQueue {
         unsafe { Context::from_raw(raw) }
     }
 
+    #[inline]
+    pub fn device(&self) -> Device {
+        let mut raw = null_mut();
+        let mut size = 0;
+        cl!(clGetCommandQueueInfo(
+            self.raw,
+            CL_QUEUE_DEVICE,
+            size_of_val(&raw),
+            &mut raw as *mut _ as _,
+            &mut size,
+        ));
+        cl!(clRetainDevice(raw));
+        Device(raw)
+    }
+
     #[inline]
     pub fn wait(&self, event: &Event) {
         self.wait_raw(&[unsafe { event.as_raw() }])
@@ -69,10 +302,11 @@ impl CommandQueue {
     #[inline]
     pub(crate) fn wait_raw(&self, raw: &[cl_event]) {
         if !raw.is_empty() {
-            cl!(clEnqueueWaitForEvents(
+            cl!(clEnqueueBarrierWithWaitList(
                 self.raw,
                 raw.len() as _,
-                raw.as_ptr()
+                raw.as_ptr(),
+                null_mut()
             ))
         }
     }
@@ -82,10 +316,34 @@ impl CommandQueue {
         cl!(clFinish(self.raw))
     }
 
+    #[cfg(cl_2_0)]
     #[inline]
     pub fn fine_grain_svm(&self) -> bool {
         self.svm.fine_grain_buffer()
     }
+
+    /// 设备支持细粒度系统 SVM，核函数可以直接访问主机内存。
+    #[cfg(cl_2_0)]
+    #[inline]
+    pub fn fine_grain_system_svm(&self) -> bool {
+        self.svm.fine_grain_system()
+    }
+
+    /// 创建队列时实际使用的属性。设备不支持的提示为 `None`。
+    #[inline]
+    pub fn properties(&self) -> &QueueProperties {
+        &self.props
+    }
+
+    #[inline]
+    pub fn profiling(&self) -> bool {
+        self.props.profiling
+    }
+
+    #[inline]
+    pub fn out_of_order(&self) -> bool {
+        self.props.out_of_order
+    }
 }
 
 #[test]
@@ -95,6 +353,33 @@ fn test() {
             let ctx = device.context();
             let queue = ctx.queue();
             assert_eq!(unsafe { queue.ctx().as_raw() }, unsafe { ctx.as_raw() });
+            assert_eq!(queue.properties(), &QueueProperties::default());
+
+            let queue = ctx
+                .queue_builder()
+                .profiling(true)
+                .out_of_order(true)
+                .priority(QueuePriority::Low)
+                .build();
+            assert!(queue.profiling());
+            assert!(queue.out_of_order());
+            assert!(
+                queue.properties().priority.is_none()
+                    || device.has_extension("cl_khr_priority_hints")
+            );
+            let mut flags = 0 as cl_command_queue_properties;
+            let mut size = 0;
+            cl!(clGetCommandQueueInfo(
+                queue.as_raw(),
+                CL_QUEUE_PROPERTIES,
+                size_of_val(&flags),
+                (&raw mut flags).cast(),
+                &mut size,
+            ));
+            assert_ne!(
+                flags & CL_QUEUE_PROFILING_ENABLE as cl_command_queue_properties,
+                0
+            );
         }
     }
 }
@@ -1,9 +1,14 @@
 ﻿use crate::{
-    bindings::{cl_context, cl_device_id},
-    AsRaw, Device,
+    bindings::{cl_context, cl_context_properties, cl_device_id, cl_platform_id},
+    AsRaw, Device, Error, Platform,
 };
 use smallvec::{smallvec, SmallVec};
-use std::ptr::{null, null_mut};
+use std::{
+    ffi::{c_char, c_void, CStr},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr::null_mut,
+    slice::from_raw_parts,
+};
 
 pub struct Context {
     raw: cl_context,
@@ -13,13 +18,206 @@ pub struct Context {
 impl Device {
     #[inline]
     pub fn context(&self) -> Context {
-        Context {
-            raw: cl!(err => clCreateContext(null(), 1, &self.as_raw(), None, null_mut(), &mut err)),
-            dev: [self.clone()].into(),
+        self.try_context().unwrap()
+    }
+
+    #[inline]
+    pub fn try_context(&self) -> Result<Context, Error> {
+        Context::try_new(std::slice::from_ref(self))
+    }
+}
+
+impl Context {
+    /// 在同一平台的多个设备上创建上下文。
+    #[inline]
+    pub fn new(devices: &[Device]) -> Self {
+        Self::try_new(devices).unwrap()
+    }
+
+    #[inline]
+    pub fn try_new(devices: &[Device]) -> Result<Self, Error> {
+        Self::builder(devices).try_build()
+    }
+
+    /// 在同一平台的多个设备上创建上下文的构建器。
+    #[inline]
+    pub fn builder(devices: &[Device]) -> ContextBuilder<'_> {
+        ContextBuilder {
+            devices,
+            platform: None,
+            props: Vec::new(),
+            notify: None,
+        }
+    }
+}
+
+/// 驱动报告上下文中发生的错误时调用的回调，参数是错误信息和驱动私有的二进制数据。
+///
+/// 回调可能在驱动的线程上被调用。
+pub type ContextNotify = dyn Fn(&str, &[u8]) + Send + Sync + 'static;
+
+/// 上下文的构建器，由 [`Context::builder`] 创建。
+pub struct ContextBuilder<'a> {
+    devices: &'a [Device],
+    platform: Option<cl_platform_id>,
+    props: Vec<cl_context_properties>,
+    notify: Option<Box<ContextNotify>>,
+}
+
+impl ContextBuilder<'_> {
+    /// 指定 `CL_CONTEXT_PLATFORM`。不指定时使用设备所在的平台。
+    #[inline]
+    pub fn platform(mut self, platform: &Platform) -> Self {
+        self.platform = Some(unsafe { platform.as_raw() });
+        self
+    }
+
+    /// 设置 `CL_CONTEXT_INTEROP_USER_SYNC`，由用户负责与图形 API 共享对象的同步。
+    #[inline]
+    pub fn interop_user_sync(self, enable: bool) -> Self {
+        use crate::bindings::CL_CONTEXT_INTEROP_USER_SYNC;
+        unsafe { self.property(CL_CONTEXT_INTEROP_USER_SYNC as _, enable as _) }
+    }
+
+    /// 添加其他属性，如 OpenGL™ 等互操作扩展的属性。
+    ///
+    /// # Safety
+    ///
+    /// `value` 必须是 `key` 要求的有效值，如有效的图形 API 句柄。
+    #[inline]
+    pub unsafe fn property(
+        mut self,
+        key: cl_context_properties,
+        value: cl_context_properties,
+    ) -> Self {
+        self.props.extend([key, value]);
+        self
+    }
+
+    /// 设置驱动报告错误时的回调。
+    ///
+    /// 回调在上下文销毁时释放，OpenCL™ 3.0 以下无法得知销毁时机，回调不会被释放。
+    /// 回调中的 panic 会被捕获并忽略。
+    #[inline]
+    pub fn notify(mut self, f: impl Fn(&str, &[u8]) + Send + Sync + 'static) -> Self {
+        self.notify = Some(Box::new(f));
+        self
+    }
+
+    /// 将驱动报告的错误转发到日志。启用 `log` 特性时使用 `log::warn!`，否则输出到标准错误。
+    #[inline]
+    pub fn log_errors(self) -> Self {
+        self.notify(|info, _| {
+            #[cfg(feature = "log")]
+            log::warn!(target: "clrt", "OpenCL context error: {info}");
+            #[cfg(not(feature = "log"))]
+            eprintln!("[clrt] OpenCL context error: {info}");
+        })
+    }
+
+    #[inline]
+    pub fn build(self) -> Context {
+        self.try_build().unwrap()
+    }
+
+    /// 创建上下文。
+    ///
+    /// 没有设备时返回 [`Error::InvalidValue`]，设备不在同一平台时返回 [`Error::InvalidDevice`]。
+    pub fn try_build(self) -> Result<Context, Error> {
+        use crate::bindings::CL_CONTEXT_PLATFORM;
+
+        const API: &str = "clCreateContext";
+        let Some((first, rest)) = self.devices.split_first() else {
+            return Err(Error::InvalidValue(API));
+        };
+        let platform = unsafe { first.platform().as_raw() };
+        if rest
+            .iter()
+            .any(|d| unsafe { d.platform().as_raw() } != platform)
+        {
+            return Err(Error::InvalidDevice(API));
         }
+
+        let mut props = vec![
+            CL_CONTEXT_PLATFORM as _,
+            self.platform.unwrap_or(platform) as _,
+        ];
+        props.extend(self.props);
+        props.push(0);
+
+        let user_data = self
+            .notify
+            .map_or(null_mut(), |f| Box::into_raw(Box::new(f)));
+        let pfn_notify = if user_data.is_null() {
+            None
+        } else {
+            Some(notify as _)
+        };
+
+        let raws = self
+            .devices
+            .iter()
+            .map(|d| unsafe { d.as_raw() })
+            .collect::<SmallVec<[_; 1]>>();
+        let raw = match try_cl!(err => clCreateContext(
+            props.as_ptr(),
+            raws.len() as _,
+            raws.as_ptr(),
+            pfn_notify,
+            user_data.cast(),
+            &mut err
+        )) {
+            Ok(raw) => raw,
+            Err(e) => {
+                if !user_data.is_null() {
+                    drop(unsafe { Box::from_raw(user_data) })
+                }
+                return Err(e);
+            }
+        };
+        // 驱动可能在上下文释放后仍调用回调，所以只在上下文销毁时释放，
+        // 不支持析构回调时泄漏
+        #[cfg(cl_3_0)]
+        if !user_data.is_null() {
+            let _ = try_cl!(clSetContextDestructorCallback(
+                raw,
+                Some(free_notify),
+                user_data.cast()
+            ));
+        }
+        Ok(Context {
+            raw,
+            dev: self.devices.iter().cloned().collect(),
+        })
     }
 }
 
+extern "C" fn notify(
+    errinfo: *const c_char,
+    private_info: *const c_void,
+    cb: usize,
+    user_data: *mut c_void,
+) {
+    let f = unsafe { &*user_data.cast::<Box<ContextNotify>>() };
+    let info = if errinfo.is_null() {
+        Default::default()
+    } else {
+        unsafe { CStr::from_ptr(errinfo) }.to_string_lossy()
+    };
+    let private = if private_info.is_null() || cb == 0 {
+        &[]
+    } else {
+        unsafe { from_raw_parts(private_info.cast(), cb) }
+    };
+    // panic 不能穿过 FFI 边界
+    let _ = catch_unwind(AssertUnwindSafe(|| f(&info, private)));
+}
+
+#[cfg(cl_3_0)]
+extern "C" fn free_notify(_context: cl_context, user_data: *mut c_void) {
+    drop(unsafe { Box::from_raw(user_data.cast::<Box<ContextNotify>>()) })
+}
+
 unsafe impl Send for Context {}
 unsafe impl Sync for Context {}
 
@@ -89,10 +287,8 @@ impl Context {
 
 #[test]
 fn test() {
-    use crate::{
-        bindings::{clCreateContext, NO_ERR},
-        Platform,
-    };
+    use crate::bindings::NO_ERR;
+    use std::ptr::null;
 
     let mut nplatform = 0;
     let mut devices = Vec::new();
@@ -111,12 +307,12 @@ fn test() {
 
     let mut err = 0;
     for &raw in &raws {
-        unsafe { clCreateContext(null(), 1, &raw, None, null_mut(), &mut err) };
+        unsafe { cl_fn!(clCreateContext)(null(), 1, &raw, None, null_mut(), &mut err) };
         assert_eq!(err, NO_ERR)
     }
     if nplatform > 1 {
         unsafe {
-            clCreateContext(
+            cl_fn!(clCreateContext)(
                 null(),
                 raws.len() as _,
                 raws.as_ptr(),
@@ -129,6 +325,48 @@ fn test() {
     }
 }
 
+#[test]
+fn test_multi_device() {
+    assert!(matches!(Context::try_new(&[]), Err(Error::InvalidValue(_))));
+    let platforms = Platform::all();
+    for platform in &platforms {
+        let devices = platform.devices();
+        if devices.is_empty() {
+            continue;
+        }
+        let ctx = Context::new(&devices);
+        assert_eq!(ctx.devices().len(), devices.len());
+        assert_eq!(ctx.queues().len(), devices.len());
+    }
+    let firsts = platforms
+        .iter()
+        .filter_map(|p| p.devices().into_iter().next())
+        .collect::<Vec<_>>();
+    if firsts.len() > 1 {
+        assert!(matches!(
+            Context::try_new(&firsts),
+            Err(Error::InvalidDevice(_))
+        ))
+    }
+}
+
+#[test]
+fn test_builder() {
+    for platform in Platform::all() {
+        let devices = platform.devices();
+        if devices.is_empty() {
+            continue;
+        }
+        let ctx = Context::builder(&devices)
+            .platform(&platform)
+            .notify(|_, _| {})
+            .build();
+        assert_eq!(ctx.devices().len(), devices.len());
+
+        let _ = Context::builder(&devices[..1]).log_errors().build();
+    }
+}
+
 #[test]
 fn test_context_from_raw() {
     for platform in crate::Platform::all() {
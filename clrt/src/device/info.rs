@@ -0,0 +1,564 @@
+use super::Device;
+use crate::{
+    bindings::{
+        cl_bool, cl_device_fp_config, cl_device_type, cl_uint, cl_ulong,
+        CL_DEVICE_TYPE_ACCELERATOR, CL_DEVICE_TYPE_ALL, CL_DEVICE_TYPE_CPU, CL_DEVICE_TYPE_CUSTOM,
+        CL_DEVICE_TYPE_DEFAULT, CL_DEVICE_TYPE_GPU, CL_FP_CORRECTLY_ROUNDED_DIVIDE_SQRT,
+        CL_FP_DENORM, CL_FP_FMA, CL_FP_INF_NAN, CL_FP_ROUND_TO_INF, CL_FP_ROUND_TO_NEAREST,
+        CL_FP_ROUND_TO_ZERO, CL_FP_SOFT_FLOAT,
+    },
+    platform::Version,
+    AsRaw,
+};
+use std::{fmt, ops::BitOr};
+
+/// 设备类型，可以按位组合。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct DeviceType(cl_device_type);
+
+impl DeviceType {
+    pub const DEFAULT: Self = Self(CL_DEVICE_TYPE_DEFAULT as _);
+    pub const CPU: Self = Self(CL_DEVICE_TYPE_CPU as _);
+    pub const GPU: Self = Self(CL_DEVICE_TYPE_GPU as _);
+    pub const ACCELERATOR: Self = Self(CL_DEVICE_TYPE_ACCELERATOR as _);
+    pub const CUSTOM: Self = Self(CL_DEVICE_TYPE_CUSTOM as _);
+    pub const ALL: Self = Self(CL_DEVICE_TYPE_ALL as _);
+
+    /// 是否包含 `other` 中的所有类型。
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// 是否包含 `other` 中的任一类型。
+    #[inline]
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    #[inline]
+    pub const fn as_raw(self) -> cl_device_type {
+        self.0
+    }
+}
+
+impl BitOr for DeviceType {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl fmt::Display for DeviceType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if *self == Self::ALL {
+            return write!(f, "All");
+        }
+        let names = [
+            (Self::CPU, "CPU"),
+            (Self::GPU, "GPU"),
+            (Self::ACCELERATOR, "Accelerator"),
+            (Self::CUSTOM, "Custom"),
+            (Self::DEFAULT, "Default"),
+        ];
+        write_flags(
+            f,
+            names
+                .iter()
+                .filter(|(ty, _)| self.contains(*ty))
+                .map(|(_, name)| *name),
+        )
+    }
+}
+
+/// 设备的浮点能力。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct FpConfig(cl_device_fp_config);
+
+impl From<cl_device_fp_config> for FpConfig {
+    #[inline]
+    fn from(config: cl_device_fp_config) -> Self {
+        Self(config)
+    }
+}
+
+impl FpConfig {
+    /// 设备是否支持该精度。
+    #[inline]
+    pub fn supported(&self) -> bool {
+        self.0 != 0
+    }
+
+    #[inline]
+    pub fn denorm(&self) -> bool {
+        self.has(CL_FP_DENORM)
+    }
+
+    #[inline]
+    pub fn inf_nan(&self) -> bool {
+        self.has(CL_FP_INF_NAN)
+    }
+
+    #[inline]
+    pub fn round_to_nearest(&self) -> bool {
+        self.has(CL_FP_ROUND_TO_NEAREST)
+    }
+
+    #[inline]
+    pub fn round_to_zero(&self) -> bool {
+        self.has(CL_FP_ROUND_TO_ZERO)
+    }
+
+    #[inline]
+    pub fn round_to_inf(&self) -> bool {
+        self.has(CL_FP_ROUND_TO_INF)
+    }
+
+    #[inline]
+    pub fn fma(&self) -> bool {
+        self.has(CL_FP_FMA)
+    }
+
+    /// 基础浮点运算由软件实现。
+    #[inline]
+    pub fn soft_float(&self) -> bool {
+        self.has(CL_FP_SOFT_FLOAT)
+    }
+
+    #[inline]
+    pub fn correctly_rounded_divide_sqrt(&self) -> bool {
+        self.has(CL_FP_CORRECTLY_ROUNDED_DIVIDE_SQRT)
+    }
+
+    #[inline]
+    fn has(&self, flag: u32) -> bool {
+        self.0 & flag as cl_device_fp_config != 0
+    }
+}
+
+impl fmt::Display for FpConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names = [
+            (self.denorm(), "Denorm"),
+            (self.inf_nan(), "Inf-NaN"),
+            (self.round_to_nearest(), "RTE"),
+            (self.round_to_zero(), "RTZ"),
+            (self.round_to_inf(), "RTI"),
+            (self.fma(), "FMA"),
+            (self.soft_float(), "Soft"),
+            (self.correctly_rounded_divide_sqrt(), "Div-Sqrt"),
+        ];
+        write_flags(
+            f,
+            names.iter().filter(|(has, _)| *has).map(|(_, name)| *name),
+        )
+    }
+}
+
+fn write_flags<'a>(
+    f: &mut fmt::Formatter,
+    mut names: impl Iterator<Item = &'a str>,
+) -> fmt::Result {
+    match names.next() {
+        Some(first) => {
+            write!(f, "{first}")?;
+            for name in names {
+                write!(f, " + {name}")?
+            }
+            Ok(())
+        }
+        None => write!(f, "None"),
+    }
+}
+
+/// 各标量类型的向量宽度，不支持的类型为 0。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VectorWidths {
+    pub char: u32,
+    pub short: u32,
+    pub int: u32,
+    pub long: u32,
+    pub half: u32,
+    pub float: u32,
+    pub double: u32,
+}
+
+impl fmt::Display for VectorWidths {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "char{} short{} int{} long{} half{} float{} double{}",
+            self.char, self.short, self.int, self.long, self.half, self.float, self.double,
+        )
+    }
+}
+
+/// 设备的图像尺寸和参数数量限制。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageLimits {
+    /// 二维图像的最大宽高。
+    pub max_2d: [usize; 2],
+    /// 三维图像的最大宽高深。
+    pub max_3d: [usize; 3],
+    /// 从缓冲区创建的一维图像的最大像素数。
+    pub max_buffer_size: usize,
+    /// 图像数组的最大图像数。
+    pub max_array_size: usize,
+    pub max_read_args: u32,
+    pub max_write_args: u32,
+    pub max_samplers: u32,
+}
+
+impl Device {
+    #[inline]
+    pub fn device_type(&self) -> DeviceType {
+        use crate::bindings::CL_DEVICE_TYPE;
+        DeviceType(self.query_value(CL_DEVICE_TYPE))
+    }
+
+    #[inline]
+    pub fn vendor(&self) -> String {
+        use crate::bindings::CL_DEVICE_VENDOR;
+        self.query_string(CL_DEVICE_VENDOR)
+    }
+
+    /// 厂商标识，通常是 PCIe 厂商 ID。
+    #[inline]
+    pub fn vendor_id(&self) -> u32 {
+        use crate::bindings::CL_DEVICE_VENDOR_ID;
+        self.query_value::<cl_uint>(CL_DEVICE_VENDOR_ID)
+    }
+
+    /// 编译器支持的最高 OpenCL™ C 版本。
+    #[inline]
+    pub fn opencl_c_version(&self) -> Version {
+        use crate::bindings::CL_DEVICE_OPENCL_C_VERSION;
+        Version::parse_lossy(&self.query_string(CL_DEVICE_OPENCL_C_VERSION))
+    }
+
+    #[inline]
+    pub fn extensions(&self) -> Vec<String> {
+        use crate::bindings::CL_DEVICE_EXTENSIONS;
+        self.query_string(CL_DEVICE_EXTENSIONS)
+            .split_whitespace()
+            .map(String::from)
+            .collect()
+    }
+
+    #[inline]
+    pub fn compute_units(&self) -> u32 {
+        use crate::bindings::CL_DEVICE_MAX_COMPUTE_UNITS;
+        self.query_value::<cl_uint>(CL_DEVICE_MAX_COMPUTE_UNITS)
+    }
+
+    /// 最大时钟频率（MHz）。
+    #[inline]
+    pub fn max_clock_frequency(&self) -> u32 {
+        use crate::bindings::CL_DEVICE_MAX_CLOCK_FREQUENCY;
+        self.query_value::<cl_uint>(CL_DEVICE_MAX_CLOCK_FREQUENCY)
+    }
+
+    #[inline]
+    pub fn global_mem_size(&self) -> u64 {
+        use crate::bindings::CL_DEVICE_GLOBAL_MEM_SIZE;
+        self.query_value::<cl_ulong>(CL_DEVICE_GLOBAL_MEM_SIZE)
+    }
+
+    #[inline]
+    pub fn local_mem_size(&self) -> u64 {
+        use crate::bindings::CL_DEVICE_LOCAL_MEM_SIZE;
+        self.query_value::<cl_ulong>(CL_DEVICE_LOCAL_MEM_SIZE)
+    }
+
+    #[inline]
+    pub fn max_constant_buffer_size(&self) -> u64 {
+        use crate::bindings::CL_DEVICE_MAX_CONSTANT_BUFFER_SIZE;
+        self.query_value::<cl_ulong>(CL_DEVICE_MAX_CONSTANT_BUFFER_SIZE)
+    }
+
+    /// 单个内存对象的最大字节数。
+    #[inline]
+    pub fn max_mem_alloc_size(&self) -> u64 {
+        use crate::bindings::CL_DEVICE_MAX_MEM_ALLOC_SIZE;
+        self.query_value::<cl_ulong>(CL_DEVICE_MAX_MEM_ALLOC_SIZE)
+    }
+
+    /// 每个维度上工作组的最大工作项数。
+    #[inline]
+    pub fn max_work_item_sizes(&self) -> Vec<usize> {
+        use crate::bindings::CL_DEVICE_MAX_WORK_ITEM_SIZES;
+        self.query_array(CL_DEVICE_MAX_WORK_ITEM_SIZES)
+    }
+
+    pub fn preferred_vector_widths(&self) -> VectorWidths {
+        use crate::bindings::*;
+        VectorWidths {
+            char: self.query_value(CL_DEVICE_PREFERRED_VECTOR_WIDTH_CHAR),
+            short: self.query_value(CL_DEVICE_PREFERRED_VECTOR_WIDTH_SHORT),
+            int: self.query_value(CL_DEVICE_PREFERRED_VECTOR_WIDTH_INT),
+            long: self.query_value(CL_DEVICE_PREFERRED_VECTOR_WIDTH_LONG),
+            half: self.query_value(CL_DEVICE_PREFERRED_VECTOR_WIDTH_HALF),
+            float: self.query_value(CL_DEVICE_PREFERRED_VECTOR_WIDTH_FLOAT),
+            double: self.query_value(CL_DEVICE_PREFERRED_VECTOR_WIDTH_DOUBLE),
+        }
+    }
+
+    pub fn native_vector_widths(&self) -> VectorWidths {
+        use crate::bindings::*;
+        VectorWidths {
+            char: self.query_value(CL_DEVICE_NATIVE_VECTOR_WIDTH_CHAR),
+            short: self.query_value(CL_DEVICE_NATIVE_VECTOR_WIDTH_SHORT),
+            int: self.query_value(CL_DEVICE_NATIVE_VECTOR_WIDTH_INT),
+            long: self.query_value(CL_DEVICE_NATIVE_VECTOR_WIDTH_LONG),
+            half: self.query_value(CL_DEVICE_NATIVE_VECTOR_WIDTH_HALF),
+            float: self.query_value(CL_DEVICE_NATIVE_VECTOR_WIDTH_FLOAT),
+            double: self.query_value(CL_DEVICE_NATIVE_VECTOR_WIDTH_DOUBLE),
+        }
+    }
+
+    /// 半精度浮点能力。不支持 `cl_khr_fp16` 时为空。
+    #[inline]
+    pub fn half_fp_config(&self) -> FpConfig {
+        use crate::bindings::CL_DEVICE_HALF_FP_CONFIG;
+        if !self.has_extension("cl_khr_fp16") {
+            return FpConfig::default();
+        }
+        FpConfig(self.query_value(CL_DEVICE_HALF_FP_CONFIG))
+    }
+
+    #[inline]
+    pub fn single_fp_config(&self) -> FpConfig {
+        use crate::bindings::CL_DEVICE_SINGLE_FP_CONFIG;
+        FpConfig(self.query_value(CL_DEVICE_SINGLE_FP_CONFIG))
+    }
+
+    /// 双精度浮点能力。不支持双精度时为空。
+    #[inline]
+    pub fn double_fp_config(&self) -> FpConfig {
+        use crate::bindings::CL_DEVICE_DOUBLE_FP_CONFIG;
+        FpConfig(self.query_value(CL_DEVICE_DOUBLE_FP_CONFIG))
+    }
+
+    #[inline]
+    pub fn little_endian(&self) -> bool {
+        use crate::bindings::CL_DEVICE_ENDIAN_LITTLE;
+        self.query_value::<cl_bool>(CL_DEVICE_ENDIAN_LITTLE) != 0
+    }
+
+    #[inline]
+    pub fn image_support(&self) -> bool {
+        use crate::bindings::CL_DEVICE_IMAGE_SUPPORT;
+        self.query_value::<cl_bool>(CL_DEVICE_IMAGE_SUPPORT) != 0
+    }
+
+    /// 图像限制。设备不支持图像时返回 `None`。
+    pub fn image_limits(&self) -> Option<ImageLimits> {
+        use crate::bindings::*;
+        if !self.image_support() {
+            return None;
+        }
+        Some(ImageLimits {
+            max_2d: [
+                self.query_value(CL_DEVICE_IMAGE2D_MAX_WIDTH),
+                self.query_value(CL_DEVICE_IMAGE2D_MAX_HEIGHT),
+            ],
+            max_3d: [
+                self.query_value(CL_DEVICE_IMAGE3D_MAX_WIDTH),
+                self.query_value(CL_DEVICE_IMAGE3D_MAX_HEIGHT),
+                self.query_value(CL_DEVICE_IMAGE3D_MAX_DEPTH),
+            ],
+            max_buffer_size: self.query_value(CL_DEVICE_IMAGE_MAX_BUFFER_SIZE),
+            max_array_size: self.query_value(CL_DEVICE_IMAGE_MAX_ARRAY_SIZE),
+            max_read_args: self.query_value(CL_DEVICE_MAX_READ_IMAGE_ARGS),
+            max_write_args: self.query_value(CL_DEVICE_MAX_WRITE_IMAGE_ARGS),
+            max_samplers: self.query_value(CL_DEVICE_MAX_SAMPLERS),
+        })
+    }
+
+    /// 工作组中子组的最大数量。OpenCL™ 2.1 以下的设备为 0。
+    #[cfg(cl_2_1)]
+    #[inline]
+    pub fn max_sub_groups(&self) -> u32 {
+        use crate::bindings::CL_DEVICE_MAX_NUM_SUB_GROUPS;
+        if self.require(2, 1, "clGetKernelSubGroupInfo").is_err() {
+            return 0;
+        }
+        self.query_value::<cl_uint>(CL_DEVICE_MAX_NUM_SUB_GROUPS)
+    }
+
+    /// 设备支持的子组大小，需要设备支持 `cl_intel_required_subgroup_size`，否则为空。
+    #[inline]
+    pub fn sub_group_sizes(&self) -> Vec<usize> {
+        /// `cl_ext_intel.h` 中的 `CL_DEVICE_SUB_GROUP_SIZES_INTEL`。
+        const CL_DEVICE_SUB_GROUP_SIZES_INTEL: cl_uint = 0x4108;
+        if !self.has_extension("cl_intel_required_subgroup_size") {
+            return Vec::new();
+        }
+        self.query_array(CL_DEVICE_SUB_GROUP_SIZES_INTEL)
+    }
+
+    /// 一次性查询设备的全部信息。
+    pub fn info(&self) -> DeviceInfo {
+        DeviceInfo {
+            name: self.name(),
+            device_type: self.device_type(),
+            vendor: self.vendor(),
+            vendor_id: self.vendor_id(),
+            version: self.version(),
+            driver_version: self.driver_version(),
+            opencl_c_version: self.opencl_c_version(),
+            extensions: self.extensions(),
+            compute_units: self.compute_units(),
+            max_clock_frequency: self.max_clock_frequency(),
+            global_mem_size: self.global_mem_size(),
+            local_mem_size: self.local_mem_size(),
+            max_constant_buffer_size: self.max_constant_buffer_size(),
+            max_mem_alloc_size: self.max_mem_alloc_size(),
+            max_work_item_sizes: self.max_work_item_sizes(),
+            max_group_size: self.max_group_size(),
+            preferred_vector_widths: self.preferred_vector_widths(),
+            native_vector_widths: self.native_vector_widths(),
+            half_fp_config: self.half_fp_config(),
+            single_fp_config: self.single_fp_config(),
+            double_fp_config: self.double_fp_config(),
+            little_endian: self.little_endian(),
+            image_limits: self.image_limits(),
+            #[cfg(cl_2_0)]
+            svm_capabilities: self.svm_capabilities(),
+            #[cfg(cl_2_1)]
+            max_sub_groups: self.max_sub_groups(),
+            sub_group_sizes: self.sub_group_sizes(),
+        }
+    }
+}
+
+/// 设备信息的快照，由 [`Device::info`] 创建。
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceInfo {
+    pub name: String,
+    pub device_type: DeviceType,
+    pub vendor: String,
+    pub vendor_id: u32,
+    pub version: Version,
+    pub driver_version: String,
+    pub opencl_c_version: Version,
+    pub extensions: Vec<String>,
+    pub compute_units: u32,
+    /// 最大时钟频率（MHz）。
+    pub max_clock_frequency: u32,
+    pub global_mem_size: u64,
+    pub local_mem_size: u64,
+    pub max_constant_buffer_size: u64,
+    pub max_mem_alloc_size: u64,
+    pub max_work_item_sizes: Vec<usize>,
+    pub max_group_size: usize,
+    pub preferred_vector_widths: VectorWidths,
+    pub native_vector_widths: VectorWidths,
+    pub half_fp_config: FpConfig,
+    pub single_fp_config: FpConfig,
+    pub double_fp_config: FpConfig,
+    pub little_endian: bool,
+    /// 设备不支持图像时为 `None`。
+    pub image_limits: Option<ImageLimits>,
+    #[cfg(cl_2_0)]
+    pub svm_capabilities: crate::SvmCapabilities,
+    #[cfg(cl_2_1)]
+    pub max_sub_groups: u32,
+    pub sub_group_sizes: Vec<usize>,
+}
+
+impl fmt::Display for DeviceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        struct Size(u64);
+        impl fmt::Display for Size {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self.0 {
+                    n if n >= 1 << 30 => write!(f, "{:.1} GiB", n as f64 / (1u64 << 30) as f64),
+                    n if n >= 1 << 20 => write!(f, "{:.1} MiB", n as f64 / (1u64 << 20) as f64),
+                    n if n >= 1 << 10 => write!(f, "{:.1} KiB", n as f64 / (1u64 << 10) as f64),
+                    n => write!(f, "{n} B"),
+                }
+            }
+        }
+
+        writeln!(f, "{} ({})", self.name, self.version)?;
+        writeln!(f, "  type: {}", self.device_type)?;
+        writeln!(f, "  vendor: {} ({:#x})", self.vendor, self.vendor_id)?;
+        writeln!(f, "  driver: {}", self.driver_version)?;
+        writeln!(
+            f,
+            "  OpenCL C: {}.{}",
+            self.opencl_c_version.major(),
+            self.opencl_c_version.minor()
+        )?;
+        writeln!(
+            f,
+            "  compute units: {} @ {} MHz",
+            self.compute_units, self.max_clock_frequency
+        )?;
+        writeln!(
+            f,
+            "  memory: global {}, local {}, constant {}, max alloc {}",
+            Size(self.global_mem_size),
+            Size(self.local_mem_size),
+            Size(self.max_constant_buffer_size),
+            Size(self.max_mem_alloc_size),
+        )?;
+        writeln!(
+            f,
+            "  work items: {:?}, max group size {}",
+            self.max_work_item_sizes, self.max_group_size
+        )?;
+        writeln!(f, "  preferred vector: {}", self.preferred_vector_widths)?;
+        writeln!(f, "  native vector: {}", self.native_vector_widths)?;
+        writeln!(
+            f,
+            "  fp16: {}, fp32: {}, fp64: {}",
+            self.half_fp_config, self.single_fp_config, self.double_fp_config
+        )?;
+        writeln!(
+            f,
+            "  endian: {}",
+            if self.little_endian { "little" } else { "big" }
+        )?;
+        match &self.image_limits {
+            Some(limits) => writeln!(
+                f,
+                "  image: 2d {:?}, 3d {:?}, buffer {}, array {}",
+                limits.max_2d, limits.max_3d, limits.max_buffer_size, limits.max_array_size
+            )?,
+            None => writeln!(f, "  image: None")?,
+        }
+        #[cfg(cl_2_0)]
+        writeln!(f, "  SVM: {}", self.svm_capabilities)?;
+        #[cfg(cl_2_1)]
+        writeln!(f, "  max sub groups: {}", self.max_sub_groups)?;
+        if !self.sub_group_sizes.is_empty() {
+            writeln!(f, "  sub group sizes: {:?}", self.sub_group_sizes)?;
+        }
+        write!(f, "  extensions: {}", self.extensions.join(" "))
+    }
+}
+
+#[test]
+fn test() {
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let info = device.info();
+            println!("{info}");
+
+            assert_eq!(info.max_work_item_sizes.len(), device.max_work_dim());
+            assert!(DeviceType::ALL.contains(info.device_type));
+            assert!(info.single_fp_config.supported());
+            assert_eq!(info.image_limits.is_some(), device.image_support());
+        }
+    }
+}
@@ -0,0 +1,160 @@
+﻿mod info;
+mod partition;
+mod select;
+
+use crate::{
+    bindings::{cl_device_id, cl_uint, CL_DEVICE_NAME},
+    platform::Version,
+    AsRaw, Error, Platform,
+};
+use std::{ffi::c_void, ptr::null_mut};
+
+pub use info::{DeviceInfo, DeviceType, FpConfig, ImageLimits, VectorWidths};
+pub use partition::{AffinityDomain, Partition};
+pub use select::DeviceSelector;
+
+#[repr(transparent)]
+pub struct Device(pub(crate) cl_device_id);
+
+impl Platform {
+    #[inline]
+    pub fn devices(&self) -> Vec<Device> {
+        self.devices_of(DeviceType::ALL)
+    }
+
+    /// 列出平台上属于 `ty` 中任一类型的设备。
+    pub fn devices_of(&self, ty: DeviceType) -> Vec<Device> {
+        let mut num = 0;
+        // 没有匹配的设备时返回 CL_DEVICE_NOT_FOUND
+        unsafe { cl_fn!(clGetDeviceIDs)(self.as_raw(), ty.as_raw(), 0, null_mut(), &mut num) };
+        if num == 0 {
+            return Vec::new();
+        }
+
+        let mut ans = vec![null_mut(); num as _];
+        unsafe {
+            cl_fn!(clGetDeviceIDs)(
+                self.as_raw(),
+                ty.as_raw(),
+                ans.len() as _,
+                ans.as_mut_ptr(),
+                &mut num,
+            )
+        };
+        assert_eq!(num, ans.len() as _);
+
+        ans.into_iter().map(Device).collect()
+    }
+}
+
+unsafe impl Send for Device {}
+unsafe impl Sync for Device {}
+
+impl Clone for Device {
+    fn clone(&self) -> Self {
+        cl!(clRetainDevice(self.0));
+        Self(self.0)
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        cl!(clReleaseDevice(self.0))
+    }
+}
+
+impl AsRaw for Device {
+    type Raw = cl_device_id;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0
+    }
+
+    #[inline]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn query(&self, key: cl_uint, val_size: usize, val: *mut c_void, size_ret: &mut usize) {
+        cl!(clGetDeviceInfo(self.as_raw(), key, val_size, val, size_ret))
+    }
+}
+
+impl Device {
+    #[inline]
+    pub fn name(&self) -> String {
+        self.query_string(CL_DEVICE_NAME)
+    }
+
+    #[inline]
+    pub fn driver_version(&self) -> String {
+        use crate::bindings::CL_DRIVER_VERSION;
+        self.query_string(CL_DRIVER_VERSION)
+    }
+
+    #[inline]
+    pub fn has_extension(&self, name: &str) -> bool {
+        use crate::bindings::CL_DEVICE_EXTENSIONS;
+        self.query_string(CL_DEVICE_EXTENSIONS)
+            .split_whitespace()
+            .any(|ext| ext == name)
+    }
+
+    #[inline]
+    pub fn platform(&self) -> Platform {
+        use crate::bindings::{cl_platform_id, CL_DEVICE_PLATFORM};
+        Platform(self.query_value::<cl_platform_id>(CL_DEVICE_PLATFORM))
+    }
+
+    /// 设备支持的 OpenCL™ 版本。
+    #[inline]
+    pub fn version(&self) -> Version {
+        use crate::bindings::CL_DEVICE_VERSION;
+        Version::parse_lossy(&self.query_string(CL_DEVICE_VERSION))
+    }
+
+    /// 检查设备和所在平台都支持 `major.minor` 版本的 API，否则返回 [`Error::Unsupported`]。
+    pub(crate) fn require(&self, major: u32, minor: u32, api: &'static str) -> Result<(), Error> {
+        let required = Version::new(major, minor);
+        if self.version() >= required && self.platform().version() >= required {
+            Ok(())
+        } else {
+            Err(Error::Unsupported(api))
+        }
+    }
+
+    /// 设备的 SVM 能力。OpenCL™ 2.0 以下的设备不支持 SVM。
+    #[cfg(cl_2_0)]
+    #[inline]
+    pub fn svm_capabilities(&self) -> crate::SvmCapabilities {
+        use crate::bindings::{cl_device_svm_capabilities, CL_DEVICE_SVM_CAPABILITIES};
+        if self.require(2, 0, "clSVMAlloc").is_err() {
+            return 0.into();
+        }
+        self.query_value::<cl_device_svm_capabilities>(CL_DEVICE_SVM_CAPABILITIES)
+            .into()
+    }
+
+    #[inline]
+    pub fn max_work_dim(&self) -> usize {
+        use crate::bindings::CL_DEVICE_MAX_WORK_ITEM_DIMENSIONS;
+        self.query_value::<cl_uint>(CL_DEVICE_MAX_WORK_ITEM_DIMENSIONS) as _
+    }
+
+    #[inline]
+    pub fn max_group_size(&self) -> usize {
+        use crate::bindings::CL_DEVICE_MAX_WORK_GROUP_SIZE;
+        self.query_value(CL_DEVICE_MAX_WORK_GROUP_SIZE)
+    }
+}
+
+#[test]
+fn probe_devices() {
+    for platform in crate::Platform::all() {
+        println!("{} ({})", platform.name(), platform.version());
+        for device in platform.devices() {
+            println!("  - {} ({})", device.name(), device.version());
+            #[cfg(cl_2_0)]
+            println!("    - SVM: {}", device.svm_capabilities());
+            println!("    - max work dim: {}", device.max_work_dim());
+            println!("    - max group size: {}", device.max_group_size());
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use super::Device;
+use crate::{
+    bindings::{
+        cl_device_affinity_domain, cl_device_id, cl_device_partition_property, cl_uint,
+        CL_DEVICE_PARTITION_BY_AFFINITY_DOMAIN, CL_DEVICE_PARTITION_BY_COUNTS,
+        CL_DEVICE_PARTITION_BY_COUNTS_LIST_END, CL_DEVICE_PARTITION_EQUALLY,
+    },
+    AsRaw, Error,
+};
+use std::ptr::null_mut;
+
+cl_enum! {
+    /// 按亲和域划分设备时使用的域。
+    pub enum AffinityDomain: cl_uint {
+        Numa              = CL_DEVICE_AFFINITY_DOMAIN_NUMA,
+        L4Cache           = CL_DEVICE_AFFINITY_DOMAIN_L4_CACHE,
+        L3Cache           = CL_DEVICE_AFFINITY_DOMAIN_L3_CACHE,
+        L2Cache           = CL_DEVICE_AFFINITY_DOMAIN_L2_CACHE,
+        L1Cache           = CL_DEVICE_AFFINITY_DOMAIN_L1_CACHE,
+        /// 沿 NUMA、L4、L3、L2、L1 的顺序选择第一个可划分的域。
+        NextPartitionable = CL_DEVICE_AFFINITY_DOMAIN_NEXT_PARTITIONABLE,
+    }
+}
+
+/// 划分子设备的方式。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Partition {
+    /// 划分为尽可能多的子设备，每个子设备有指定数量的计算单元。
+    Equally(u32),
+    /// 按列表中每一项的计算单元数划分子设备。
+    ByCounts(Vec<u32>),
+    /// 按亲和域划分子设备。
+    ByAffinityDomain(AffinityDomain),
+}
+
+impl Partition {
+    fn to_properties(&self) -> Vec<cl_device_partition_property> {
+        let mut props = Vec::new();
+        match self {
+            Self::Equally(n) => props.extend([
+                CL_DEVICE_PARTITION_EQUALLY as cl_device_partition_property,
+                *n as _,
+            ]),
+            Self::ByCounts(counts) => {
+                props.push(CL_DEVICE_PARTITION_BY_COUNTS as _);
+                props.extend(counts.iter().map(|&n| n as cl_device_partition_property));
+                props.push(CL_DEVICE_PARTITION_BY_COUNTS_LIST_END as _);
+            }
+            Self::ByAffinityDomain(domain) => props.extend([
+                CL_DEVICE_PARTITION_BY_AFFINITY_DOMAIN as cl_device_partition_property,
+                domain.as_raw() as _,
+            ]),
+        }
+        props.push(0);
+        props
+    }
+
+    fn from_properties(props: &[cl_device_partition_property]) -> Option<Self> {
+        let (&kind, rest) = props.split_first()?;
+        match kind as cl_uint {
+            CL_DEVICE_PARTITION_EQUALLY => Some(Self::Equally(*rest.first()? as _)),
+            CL_DEVICE_PARTITION_BY_COUNTS => Some(Self::ByCounts(
+                rest.iter()
+                    .take_while(|&&n| n != CL_DEVICE_PARTITION_BY_COUNTS_LIST_END as _)
+                    .map(|&n| n as _)
+                    .collect(),
+            )),
+            CL_DEVICE_PARTITION_BY_AFFINITY_DOMAIN => {
+                AffinityDomain::from_raw(*rest.first()? as _).map(Self::ByAffinityDomain)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Device {
+    /// 按 `partition` 划分子设备。
+    #[inline]
+    pub fn partition(&self, partition: &Partition) -> Vec<Device> {
+        self.try_partition(partition).unwrap()
+    }
+
+    pub fn try_partition(&self, partition: &Partition) -> Result<Vec<Device>, Error> {
+        let props = partition.to_properties();
+        let mut num = 0;
+        try_cl!(clCreateSubDevices(
+            self.as_raw(),
+            props.as_ptr(),
+            0,
+            null_mut(),
+            &mut num
+        ))?;
+
+        let mut ans: Vec<cl_device_id> = vec![null_mut(); num as _];
+        try_cl!(clCreateSubDevices(
+            self.as_raw(),
+            props.as_ptr(),
+            num,
+            ans.as_mut_ptr(),
+            &mut num
+        ))?;
+        assert_eq!(num as usize, ans.len());
+
+        Ok(ans.into_iter().map(Device).collect())
+    }
+
+    /// 子设备的父设备，根设备返回 `None`。
+    pub fn parent(&self) -> Option<Device> {
+        use crate::bindings::CL_DEVICE_PARENT_DEVICE;
+        let raw = self.query_value::<cl_device_id>(CL_DEVICE_PARENT_DEVICE);
+        if raw.is_null() {
+            None
+        } else {
+            cl!(clRetainDevice(raw));
+            Some(Device(raw))
+        }
+    }
+
+    /// 创建这个子设备时使用的划分方式，根设备返回 `None`。
+    #[inline]
+    pub fn partition_type(&self) -> Option<Partition> {
+        use crate::bindings::CL_DEVICE_PARTITION_TYPE;
+        Partition::from_properties(&self.query_array(CL_DEVICE_PARTITION_TYPE))
+    }
+
+    /// 最多可以划分的子设备数，不支持划分时为 0。
+    #[inline]
+    pub fn max_sub_devices(&self) -> u32 {
+        use crate::bindings::CL_DEVICE_PARTITION_MAX_SUB_DEVICES;
+        self.query_value::<cl_uint>(CL_DEVICE_PARTITION_MAX_SUB_DEVICES)
+    }
+
+    /// 设备支持的亲和域。
+    pub fn affinity_domains(&self) -> Vec<AffinityDomain> {
+        use crate::bindings::CL_DEVICE_PARTITION_AFFINITY_DOMAIN;
+        let domains =
+            self.query_value::<cl_device_affinity_domain>(CL_DEVICE_PARTITION_AFFINITY_DOMAIN);
+        (0..cl_uint::BITS)
+            .map(|i| 1 << i)
+            .filter(|bit| domains & *bit as cl_device_affinity_domain != 0)
+            .filter_map(AffinityDomain::from_raw)
+            .collect()
+    }
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Partition::from_properties(&Partition::ByCounts(vec![2, 1]).to_properties()),
+        Some(Partition::ByCounts(vec![2, 1]))
+    );
+
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            assert!(device.parent().is_none());
+            assert_eq!(device.partition_type(), None);
+            if device.max_sub_devices() < 2 {
+                continue;
+            }
+
+            let n = device.compute_units() / 2;
+            let subs = device.partition(&Partition::Equally(n));
+            assert!(subs.len() >= 2);
+            for sub in &subs {
+                let parent = sub.parent().unwrap();
+                assert_eq!(unsafe { parent.as_raw() }, unsafe { device.as_raw() });
+                assert_eq!(sub.partition_type(), Some(Partition::Equally(n)));
+                assert_eq!(sub.compute_units(), n);
+
+                let ctx = sub.context();
+                let _ = ctx.queue();
+            }
+
+            let subs = device.partition(&Partition::ByCounts(vec![1, 1]));
+            assert_eq!(subs.len(), 2);
+            for domain in device.affinity_domains() {
+                match device.try_partition(&Partition::ByAffinityDomain(domain)) {
+                    Ok(subs) => println!("{domain:?}: {} sub-devices", subs.len()),
+                    Err(e) => println!("{domain:?}: {e}"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use super::{Device, DeviceType};
+use crate::Platform;
+use std::env;
+
+/// 指定设备的环境变量，格式为 `platform:device`。
+///
+/// 两部分都可以是序号或名字中的片段（不区分大小写），省略时匹配任意平台或设备，如 `0:1`、`nvidia:`、`:a100`。
+const ENV_DEVICE: &str = "CLRT_DEVICE";
+
+/// 跨平台选择设备的策略，由 [`Device::selector`] 创建。
+///
+/// 设置了环境变量 `CLRT_DEVICE` 时优先使用其指定的设备，忽略其他条件。
+pub struct DeviceSelector {
+    ty: DeviceType,
+    filters: Vec<Filter>,
+    key: Option<Key>,
+    env: bool,
+}
+
+type Filter = Box<dyn Fn(&Device) -> bool>;
+type Key = Box<dyn Fn(&Device) -> u64>;
+
+impl Device {
+    #[inline]
+    pub fn selector() -> DeviceSelector {
+        DeviceSelector {
+            ty: DeviceType::ALL,
+            filters: Vec::new(),
+            key: None,
+            env: true,
+        }
+    }
+}
+
+impl DeviceSelector {
+    /// 只选择属于 `ty` 中任一类型的设备。
+    #[inline]
+    pub fn device_type(mut self, ty: DeviceType) -> Self {
+        self.ty = ty;
+        self
+    }
+
+    /// 只选择满足 `f` 的设备。
+    #[inline]
+    pub fn filter(mut self, f: impl Fn(&Device) -> bool + 'static) -> Self {
+        self.filters.push(Box::new(f));
+        self
+    }
+
+    /// 只选择支持扩展 `name` 的设备。
+    #[inline]
+    pub fn extension(self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.filter(move |d| d.has_extension(&name))
+    }
+
+    /// 只选择支持细粒度 SVM 缓冲区或系统 SVM 的设备。
+    #[cfg(cl_2_0)]
+    #[inline]
+    pub fn fine_grain_svm(self) -> Self {
+        self.filter(|d| {
+            let svm = d.svm_capabilities();
+            svm.fine_grain_buffer() || svm.fine_grain_system()
+        })
+    }
+
+    /// 只选择名字匹配 `re` 的设备。
+    #[cfg(feature = "regex")]
+    #[inline]
+    pub fn name_matches(self, re: regex::Regex) -> Self {
+        self.filter(move |d| re.is_match(&d.name()))
+    }
+
+    /// 选择 `key` 最大的设备，相等时选择靠前的设备。不设置时选择第一个满足条件的设备。
+    #[inline]
+    pub fn max_by_key(mut self, key: impl Fn(&Device) -> u64 + 'static) -> Self {
+        self.key = Some(Box::new(key));
+        self
+    }
+
+    #[inline]
+    pub fn most_compute_units(self) -> Self {
+        self.max_by_key(|d| d.compute_units() as _)
+    }
+
+    #[inline]
+    pub fn largest_global_memory(self) -> Self {
+        self.max_by_key(Device::global_mem_size)
+    }
+
+    /// 忽略环境变量 `CLRT_DEVICE`。
+    #[inline]
+    pub fn ignore_env(mut self) -> Self {
+        self.env = false;
+        self
+    }
+
+    /// 按平台和设备的顺序列出满足条件的所有设备，不考虑环境变量。
+    pub fn candidates(&self) -> Vec<Device> {
+        Platform::all()
+            .iter()
+            .flat_map(|p| p.devices_of(self.ty))
+            .filter(|d| self.filters.iter().all(|f| f(d)))
+            .collect()
+    }
+
+    /// 选择设备，没有满足条件的设备时返回 `None`。
+    ///
+    /// 环境变量 `CLRT_DEVICE` 的格式错误时输出到日志并返回 `None`。
+    pub fn select(&self) -> Option<Device> {
+        if self.env {
+            if let Ok(spec) = env::var(ENV_DEVICE) {
+                let Some(spec) = Spec::parse(&spec) else {
+                    #[cfg(feature = "log")]
+                    log::warn!(target: "clrt", "{ENV_DEVICE}={spec} is not `platform:device`");
+                    #[cfg(not(feature = "log"))]
+                    eprintln!("[clrt] {ENV_DEVICE}={spec} is not `platform:device`");
+                    return None;
+                };
+                return spec.select();
+            }
+        }
+
+        let candidates = self.candidates();
+        match &self.key {
+            // max_by_key 在相等时返回最后一个
+            Some(key) => candidates.into_iter().rev().max_by_key(|d| key(d)),
+            None => candidates.into_iter().next(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+enum Part {
+    Any,
+    Index(usize),
+    Name(String),
+}
+
+impl Part {
+    fn parse(s: &str) -> Self {
+        let s = s.trim();
+        if s.is_empty() {
+            Self::Any
+        } else if let Ok(i) = s.parse() {
+            Self::Index(i)
+        } else {
+            Self::Name(s.to_lowercase())
+        }
+    }
+
+    fn matches(&self, index: usize, name: impl FnOnce() -> String) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Index(i) => *i == index,
+            Self::Name(part) => name().to_lowercase().contains(part),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct Spec {
+    platform: Part,
+    device: Part,
+}
+
+impl Spec {
+    fn parse(spec: &str) -> Option<Self> {
+        let (platform, device) = spec.split_once(':')?;
+        Some(Self {
+            platform: Part::parse(platform),
+            device: Part::parse(device),
+        })
+    }
+
+    fn select(&self) -> Option<Device> {
+        Platform::all()
+            .into_iter()
+            .enumerate()
+            .filter(|(i, p)| self.platform.matches(*i, || p.name()))
+            .flat_map(|(_, p)| p.devices().into_iter().enumerate())
+            .find(|(i, d)| self.device.matches(*i, || d.name()))
+            .map(|(_, d)| d)
+    }
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Spec::parse("0:nvidia"),
+        Some(Spec {
+            platform: Part::Index(0),
+            device: Part::Name("nvidia".into()),
+        })
+    );
+    assert_eq!(
+        Spec::parse(":1"),
+        Some(Spec {
+            platform: Part::Any,
+            device: Part::Index(1),
+        })
+    );
+    assert_eq!(Spec::parse("0"), None);
+    assert!(Part::parse("A100").matches(3, || "NVIDIA A100-SXM4".into()));
+
+    let all = Device::selector().ignore_env().candidates();
+    assert_eq!(
+        all.len(),
+        Platform::all().iter().map(|p| p.devices().len()).sum()
+    );
+    if let Some(device) = Device::selector()
+        .ignore_env()
+        .device_type(DeviceType::GPU)
+        .most_compute_units()
+        .select()
+    {
+        assert!(device.device_type().contains(DeviceType::GPU));
+        for d in Platform::all()
+            .iter()
+            .flat_map(|p| p.devices_of(DeviceType::GPU))
+        {
+            assert!(d.compute_units() <= device.compute_units())
+        }
+    }
+}
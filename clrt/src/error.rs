@@ -0,0 +1,162 @@
+use crate::bindings::{cl_int, CL_INVALID_OPERATION, NO_ERR};
+use std::fmt;
+
+macro_rules! errors {
+    ($($name:ident = $code:literal: $cl:ident)+) => {
+        /// OpenCL™ API 调用失败时返回的错误，携带失败的 API 名字。
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        #[non_exhaustive]
+        pub enum Error {
+            $(
+                #[doc = concat!("`", stringify!($cl), "`")]
+                $name(&'static str),
+            )+
+            /// 未知的错误码。
+            Unknown(&'static str, cl_int),
+            /// 设备或平台的 OpenCL™ 版本不支持这个 API。
+            Unsupported(&'static str),
+        }
+
+        impl Error {
+            /// 将 `api` 返回的错误码转换为 [`Result`]。
+            pub fn check(api: &'static str, code: cl_int) -> Result<(), Self> {
+                match code {
+                    NO_ERR => Ok(()),
+                    $($code => Err(Self::$name(api)),)+
+                    code => Err(Self::Unknown(api, code)),
+                }
+            }
+
+            /// 失败的 API 名字。
+            pub fn api(&self) -> &'static str {
+                match *self {
+                    $(Self::$name(api))|+ | Self::Unknown(api, _) | Self::Unsupported(api) => api,
+                }
+            }
+
+            /// 原始错误码。
+            pub fn code(&self) -> cl_int {
+                match *self {
+                    $(Self::$name(_) => $code,)+
+                    Self::Unknown(_, code) => code,
+                    Self::Unsupported(_) => CL_INVALID_OPERATION,
+                }
+            }
+
+            /// 错误码在 OpenCL™ 头文件中的名字。
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$name(_) => stringify!($cl),)+
+                    Self::Unknown(..) => "<unknown>",
+                    Self::Unsupported(_) => "<unsupported>",
+                }
+            }
+        }
+    };
+}
+
+errors! {
+    DeviceNotFound                       =    -1: CL_DEVICE_NOT_FOUND
+    DeviceNotAvailable                   =    -2: CL_DEVICE_NOT_AVAILABLE
+    CompilerNotAvailable                 =    -3: CL_COMPILER_NOT_AVAILABLE
+    MemObjectAllocationFailure           =    -4: CL_MEM_OBJECT_ALLOCATION_FAILURE
+    OutOfResources                       =    -5: CL_OUT_OF_RESOURCES
+    OutOfHostMemory                      =    -6: CL_OUT_OF_HOST_MEMORY
+    ProfilingInfoNotAvailable            =    -7: CL_PROFILING_INFO_NOT_AVAILABLE
+    MemCopyOverlap                       =    -8: CL_MEM_COPY_OVERLAP
+    ImageFormatMismatch                  =    -9: CL_IMAGE_FORMAT_MISMATCH
+    ImageFormatNotSupported              =   -10: CL_IMAGE_FORMAT_NOT_SUPPORTED
+    BuildProgramFailure                  =   -11: CL_BUILD_PROGRAM_FAILURE
+    MapFailure                           =   -12: CL_MAP_FAILURE
+    MisalignedSubBufferOffset            =   -13: CL_MISALIGNED_SUB_BUFFER_OFFSET
+    ExecStatusErrorForEventsInWaitList   =   -14: CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST
+    CompileProgramFailure                =   -15: CL_COMPILE_PROGRAM_FAILURE
+    LinkerNotAvailable                   =   -16: CL_LINKER_NOT_AVAILABLE
+    LinkProgramFailure                   =   -17: CL_LINK_PROGRAM_FAILURE
+    DevicePartitionFailed                =   -18: CL_DEVICE_PARTITION_FAILED
+    KernelArgInfoNotAvailable            =   -19: CL_KERNEL_ARG_INFO_NOT_AVAILABLE
+    InvalidValue                         =   -30: CL_INVALID_VALUE
+    InvalidDeviceType                    =   -31: CL_INVALID_DEVICE_TYPE
+    InvalidPlatform                      =   -32: CL_INVALID_PLATFORM
+    InvalidDevice                        =   -33: CL_INVALID_DEVICE
+    InvalidContext                       =   -34: CL_INVALID_CONTEXT
+    InvalidQueueProperties               =   -35: CL_INVALID_QUEUE_PROPERTIES
+    InvalidCommandQueue                  =   -36: CL_INVALID_COMMAND_QUEUE
+    InvalidHostPtr                       =   -37: CL_INVALID_HOST_PTR
+    InvalidMemObject                     =   -38: CL_INVALID_MEM_OBJECT
+    InvalidImageFormatDescriptor         =   -39: CL_INVALID_IMAGE_FORMAT_DESCRIPTOR
+    InvalidImageSize                     =   -40: CL_INVALID_IMAGE_SIZE
+    InvalidSampler                       =   -41: CL_INVALID_SAMPLER
+    InvalidBinary                        =   -42: CL_INVALID_BINARY
+    InvalidBuildOptions                  =   -43: CL_INVALID_BUILD_OPTIONS
+    InvalidProgram                       =   -44: CL_INVALID_PROGRAM
+    InvalidProgramExecutable             =   -45: CL_INVALID_PROGRAM_EXECUTABLE
+    InvalidKernelName                    =   -46: CL_INVALID_KERNEL_NAME
+    InvalidKernelDefinition              =   -47: CL_INVALID_KERNEL_DEFINITION
+    InvalidKernel                        =   -48: CL_INVALID_KERNEL
+    InvalidArgIndex                      =   -49: CL_INVALID_ARG_INDEX
+    InvalidArgValue                      =   -50: CL_INVALID_ARG_VALUE
+    InvalidArgSize                       =   -51: CL_INVALID_ARG_SIZE
+    InvalidKernelArgs                    =   -52: CL_INVALID_KERNEL_ARGS
+    InvalidWorkDimension                 =   -53: CL_INVALID_WORK_DIMENSION
+    InvalidWorkGroupSize                 =   -54: CL_INVALID_WORK_GROUP_SIZE
+    InvalidWorkItemSize                  =   -55: CL_INVALID_WORK_ITEM_SIZE
+    InvalidGlobalOffset                  =   -56: CL_INVALID_GLOBAL_OFFSET
+    InvalidEventWaitList                 =   -57: CL_INVALID_EVENT_WAIT_LIST
+    InvalidEvent                         =   -58: CL_INVALID_EVENT
+    InvalidOperation                     =   -59: CL_INVALID_OPERATION
+    InvalidGlObject                      =   -60: CL_INVALID_GL_OBJECT
+    InvalidBufferSize                    =   -61: CL_INVALID_BUFFER_SIZE
+    InvalidMipLevel                      =   -62: CL_INVALID_MIP_LEVEL
+    InvalidGlobalWorkSize                =   -63: CL_INVALID_GLOBAL_WORK_SIZE
+    InvalidProperty                      =   -64: CL_INVALID_PROPERTY
+    InvalidImageDescriptor               =   -65: CL_INVALID_IMAGE_DESCRIPTOR
+    InvalidCompilerOptions               =   -66: CL_INVALID_COMPILER_OPTIONS
+    InvalidLinkerOptions                 =   -67: CL_INVALID_LINKER_OPTIONS
+    InvalidDevicePartitionCount          =   -68: CL_INVALID_DEVICE_PARTITION_COUNT
+    InvalidPipeSize                      =   -69: CL_INVALID_PIPE_SIZE
+    InvalidDeviceQueue                   =   -70: CL_INVALID_DEVICE_QUEUE
+    InvalidSpecId                        =   -71: CL_INVALID_SPEC_ID
+    MaxSizeRestrictionExceeded           =   -72: CL_MAX_SIZE_RESTRICTION_EXCEEDED
+    InvalidGlSharegroupReferenceKhr      = -1000: CL_INVALID_GL_SHAREGROUP_REFERENCE_KHR
+    PlatformNotFoundKhr                  = -1001: CL_PLATFORM_NOT_FOUND_KHR
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unsupported(api) => write!(f, "{api} is not supported by the device or platform"),
+            _ => write!(
+                f,
+                "{} failed: {} ({})",
+                self.api(),
+                self.name(),
+                self.code()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[test]
+fn test_check() {
+    assert_eq!(Error::check("clFinish", NO_ERR), Ok(()));
+
+    let err = Error::check("clFinish", -5).unwrap_err();
+    assert_eq!(err, Error::OutOfResources("clFinish"));
+    assert_eq!(err.code(), -5);
+    assert_eq!(err.api(), "clFinish");
+    assert_eq!(err.to_string(), "clFinish failed: CL_OUT_OF_RESOURCES (-5)");
+
+    let err = Error::check("clFinish", -9999).unwrap_err();
+    assert_eq!(err, Error::Unknown("clFinish", -9999));
+    assert_eq!(err.code(), -9999);
+
+    let err = Error::Unsupported("clSVMAlloc");
+    assert_eq!(err.api(), "clSVMAlloc");
+    assert_eq!(
+        err.to_string(),
+        "clSVMAlloc is not supported by the device or platform"
+    );
+}
@@ -0,0 +1,195 @@
+use super::{Event, EventStatus};
+use crate::{
+    bindings::{cl_event, cl_int},
+    AsRaw, Error,
+};
+use std::{
+    ffi::c_void,
+    future::{Future, IntoFuture},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// 等待事件完成的 [`Future`]，由 `Event` 的 [`IntoFuture`] 实现创建。
+///
+/// 通过 `clSetEventCallback` 唤醒，不依赖特定的异步运行时。
+pub struct EventFuture {
+    event: Event,
+    shared: Option<Arc<Mutex<Shared>>>,
+}
+
+#[derive(Default)]
+struct Shared {
+    status: Option<cl_int>,
+    waker: Option<Waker>,
+}
+
+impl IntoFuture for Event {
+    type Output = Result<(), Error>;
+    type IntoFuture = EventFuture;
+
+    #[inline]
+    fn into_future(self) -> Self::IntoFuture {
+        EventFuture {
+            event: self,
+            shared: None,
+        }
+    }
+}
+
+impl Future for EventFuture {
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let shared = match &this.shared {
+            Some(shared) => shared.clone(),
+            None => {
+                match this.event.status() {
+                    EventStatus::Complete => return Poll::Ready(Ok(())),
+                    EventStatus::Error(code) => return Poll::Ready(Err(this.event.error(code))),
+                    _ => {}
+                }
+                let shared = Arc::new(Mutex::new(Shared {
+                    status: None,
+                    waker: Some(cx.waker().clone()),
+                }));
+                // 回调可能在注册时立即在当前线程执行，因此注册时不能持有锁
+                let user_data = Arc::into_raw(shared.clone());
+                if let Err(e) = try_cl!(clSetEventCallback(
+                    this.event.0,
+                    CL_COMPLETE as _,
+                    Some(callback),
+                    user_data.cast_mut().cast(),
+                )) {
+                    // 注册失败时回调不会执行，收回交给回调的引用
+                    drop(unsafe { Arc::from_raw(user_data) });
+                    return Poll::Ready(Err(e));
+                }
+                this.shared.insert(shared).clone()
+            }
+        };
+
+        let mut shared = shared.lock().unwrap();
+        match shared.status {
+            Some(code) if code < 0 => Poll::Ready(Err(this.event.error(code))),
+            Some(_) => Poll::Ready(Ok(())),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+extern "C" fn callback(_event: cl_event, status: cl_int, user_data: *mut c_void) {
+    let shared = unsafe { Arc::from_raw(user_data.cast_const().cast::<Mutex<Shared>>()) };
+    let waker = {
+        let mut shared = shared.lock().unwrap();
+        shared.status = Some(status);
+        shared.waker.take()
+    };
+    if let Some(waker) = waker {
+        waker.wake()
+    }
+}
+
+impl Event {
+    /// 将命令的错误状态转换为以对应入队函数命名的 [`Error`]。
+    fn error(&self, code: cl_int) -> Error {
+        use crate::bindings::*;
+        let api = match self.query_value::<cl_command_type>(CL_EVENT_COMMAND_TYPE) {
+            CL_COMMAND_NDRANGE_KERNEL => "clEnqueueNDRangeKernel",
+            CL_COMMAND_READ_BUFFER => "clEnqueueReadBuffer",
+            CL_COMMAND_WRITE_BUFFER => "clEnqueueWriteBuffer",
+            CL_COMMAND_COPY_BUFFER => "clEnqueueCopyBuffer",
+            CL_COMMAND_FILL_BUFFER => "clEnqueueFillBuffer",
+            CL_COMMAND_MAP_BUFFER => "clEnqueueMapBuffer",
+            CL_COMMAND_READ_IMAGE => "clEnqueueReadImage",
+            CL_COMMAND_WRITE_IMAGE => "clEnqueueWriteImage",
+            CL_COMMAND_COPY_IMAGE => "clEnqueueCopyImage",
+            CL_COMMAND_FILL_IMAGE => "clEnqueueFillImage",
+            CL_COMMAND_MAP_IMAGE => "clEnqueueMapImage",
+            CL_COMMAND_UNMAP_MEM_OBJECT => "clEnqueueUnmapMemObject",
+            #[cfg(cl_2_0)]
+            CL_COMMAND_SVM_FREE => "clEnqueueSVMFree",
+            #[cfg(cl_2_0)]
+            CL_COMMAND_SVM_MEMCPY => "clEnqueueSVMMemcpy",
+            #[cfg(cl_2_0)]
+            CL_COMMAND_SVM_MEMFILL => "clEnqueueSVMMemFill",
+            #[cfg(cl_2_0)]
+            CL_COMMAND_SVM_MAP => "clEnqueueSVMMap",
+            #[cfg(cl_2_0)]
+            CL_COMMAND_SVM_UNMAP => "clEnqueueSVMUnmap",
+            CL_COMMAND_USER => "clSetUserEventStatus",
+            _ => "command",
+        };
+        Error::check(api, code).unwrap_err()
+    }
+}
+
+#[cfg(test)]
+fn block_on<F: Future>(fut: F) -> F::Output {
+    use std::{
+        pin::pin,
+        task::Wake,
+        thread::{self, Thread},
+    };
+
+    struct ThreadWaker(Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark()
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = pin!(fut);
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(ans) => break ans,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use std::{thread, time::Duration};
+
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let ctx = device.context();
+
+            let user_event = ctx.user_event();
+            let event = Event::clone(&user_event);
+            thread::scope(|s| {
+                s.spawn(|| {
+                    thread::sleep(Duration::from_millis(10));
+                    user_event.complete()
+                });
+                assert!(block_on(event.into_future()).is_ok());
+            });
+            // 已完成的事件立即就绪
+            assert!(block_on(Event::clone(&user_event).into_future()).is_ok());
+
+            let user_event = ctx.user_event();
+            let event = Event::clone(&user_event);
+            thread::scope(|s| {
+                s.spawn(|| user_event.fail(crate::bindings::CL_OUT_OF_RESOURCES));
+                assert!(matches!(
+                    block_on(event.into_future()),
+                    Err(Error::OutOfResources("clSetUserEventStatus"))
+                ));
+            });
+
+            let queue = ctx.queue();
+            let mut buf = ctx.buffer::<u32>(256);
+            let mut node = crate::EventNode::new([], true);
+            queue.write_buffer(&mut buf, 0, &[1u32; 256], Some(&mut node));
+            assert!(block_on(node.take().unwrap().into_future()).is_ok());
+        }
+    }
+}
@@ -0,0 +1,253 @@
+﻿mod future;
+
+use crate::{
+    bindings::{cl_event, cl_int, cl_profiling_info, cl_uint, cl_ulong},
+    AsRaw, Context, Error,
+};
+use std::{borrow::Borrow, ffi::c_void, mem::transmute, ops::Deref, time::Duration};
+
+pub use future::EventFuture;
+
+#[repr(transparent)]
+pub struct Event(pub(crate) cl_event);
+
+impl Event {
+    #[inline]
+    pub(crate) fn from_ref(event: &cl_event) -> &Self {
+        unsafe { transmute(event) }
+    }
+}
+
+impl<'a> From<&'a cl_event> for &'a Event {
+    #[inline]
+    fn from(value: &'a cl_event) -> Self {
+        Event::from_ref(value)
+    }
+}
+
+impl From<UserEvent> for Event {
+    #[inline]
+    fn from(value: UserEvent) -> Self {
+        value.0
+    }
+}
+
+unsafe impl Send for Event {}
+unsafe impl Sync for Event {}
+
+impl Drop for Event {
+    fn drop(&mut self) {
+        cl!(clReleaseEvent(self.0))
+    }
+}
+
+impl AsRaw for Event {
+    type Raw = cl_event;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0
+    }
+
+    #[inline]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn query(&self, key: cl_uint, val_size: usize, val: *mut c_void, size_ret: &mut usize) {
+        cl!(clGetEventInfo(self.0, key, val_size, val, size_ret))
+    }
+}
+
+impl Clone for Event {
+    fn clone(&self) -> Self {
+        cl!(clRetainEvent(self.0));
+        Self(self.0)
+    }
+}
+
+/// 事件对应命令的执行状态。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum EventStatus {
+    Queued,
+    Submitted,
+    Running,
+    Complete,
+    /// 命令异常终止，附带错误码。
+    Error(cl_int),
+}
+
+impl EventStatus {
+    #[inline]
+    pub fn from_raw(raw: cl_int) -> Self {
+        use crate::bindings::{CL_COMPLETE, CL_QUEUED, CL_RUNNING, CL_SUBMITTED};
+        match raw {
+            e if e < 0 => Self::Error(e),
+            e => match e as cl_uint {
+                CL_QUEUED => Self::Queued,
+                CL_SUBMITTED => Self::Submitted,
+                CL_RUNNING => Self::Running,
+                CL_COMPLETE => Self::Complete,
+                _ => unreachable!("unknown event status {e}"),
+            },
+        }
+    }
+}
+
+cl_enum! {
+    /// 事件的性能分析时间点。
+    pub enum ProfilingInfo: cl_profiling_info {
+        Queued   = CL_PROFILING_COMMAND_QUEUED,
+        Submit   = CL_PROFILING_COMMAND_SUBMIT,
+        Start    = CL_PROFILING_COMMAND_START,
+        End      = CL_PROFILING_COMMAND_END,
+        #[cfg(cl_2_0)]
+        Complete = CL_PROFILING_COMMAND_COMPLETE,
+    }
+}
+
+impl Event {
+    #[inline]
+    pub fn wait(&self) {
+        cl!(clWaitForEvents(1, &self.0))
+    }
+
+    /// 查询命令的执行状态，不会阻塞。
+    #[inline]
+    pub fn status(&self) -> EventStatus {
+        use crate::bindings::CL_EVENT_COMMAND_EXECUTION_STATUS;
+        EventStatus::from_raw(self.query_value(CL_EVENT_COMMAND_EXECUTION_STATUS))
+    }
+
+    /// 读取以纳秒为单位的设备时间戳。
+    ///
+    /// 队列未启用性能分析或命令尚未完成时返回 [`Error::ProfilingInfoNotAvailable`]。
+    pub fn timestamp(&self, info: ProfilingInfo) -> Result<u64, Error> {
+        let mut ans: cl_ulong = 0;
+        let mut size = 0;
+        try_cl!(clGetEventProfilingInfo(
+            self.0,
+            info.as_raw(),
+            size_of_val(&ans),
+            (&raw mut ans).cast(),
+            &mut size
+        ))?;
+        assert_eq!(size, size_of_val(&ans));
+        Ok(ans)
+    }
+
+    /// 命令从开始执行到结束的时间，要求同 [`Event::timestamp`]。
+    pub fn elapsed(&self) -> Result<Duration, Error> {
+        let start = self.timestamp(ProfilingInfo::Start)?;
+        let end = self.timestamp(ProfilingInfo::End)?;
+        Ok(Duration::from_nanos(end.saturating_sub(start)))
+    }
+}
+
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct UserEvent(Event);
+
+impl Context {
+    #[inline]
+    pub fn user_event(&self) -> UserEvent {
+        UserEvent(Event(
+            cl!(err => clCreateUserEvent(self.as_raw(), &mut err)),
+        ))
+    }
+}
+
+impl UserEvent {
+    #[inline]
+    pub fn complete(&self) {
+        cl!(clSetUserEventStatus(self.0 .0, CL_COMPLETE as _))
+    }
+
+    /// 以错误码 `code`（必须为负数）结束用户事件，等待它的命令将异常终止。
+    #[inline]
+    pub fn fail(&self, code: cl_int) {
+        assert!(code < 0);
+        cl!(clSetUserEventStatus(self.0 .0, code))
+    }
+}
+
+impl AsRaw for UserEvent {
+    type Raw = cl_event;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0 .0
+    }
+}
+
+impl AsRef<Event> for UserEvent {
+    #[inline]
+    fn as_ref(&self) -> &Event {
+        &self.0
+    }
+}
+
+impl Borrow<Event> for UserEvent {
+    #[inline]
+    fn borrow(&self) -> &Event {
+        &self.0
+    }
+}
+
+impl Deref for UserEvent {
+    type Target = Event;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[test]
+fn test() {
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let ctx = device.context();
+            let queue = ctx.queue();
+            let user_event = ctx.user_event();
+            // CPU 上“队列”指向的就是 ThisThread，等待队列将阻塞当前线程
+            std::thread::scope(|s| {
+                s.spawn(|| queue.wait(&user_event));
+                s.spawn(|| user_event.complete());
+            });
+            queue.finish();
+            assert_eq!(user_event.status(), EventStatus::Complete);
+            assert!(matches!(
+                user_event.elapsed(),
+                Err(Error::ProfilingInfoNotAvailable(_))
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_profiling() {
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let ctx = device.context();
+            let mut buf = ctx.buffer::<u32>(1024);
+            let host = vec![1u32; 1024];
+
+            let queue = ctx.queue();
+            let mut node = crate::EventNode::new([], true);
+            queue.write_buffer(&mut buf, 0, &host, Some(&mut node));
+            let event = node.take().unwrap();
+            event.wait();
+            assert!(matches!(
+                event.elapsed(),
+                Err(Error::ProfilingInfoNotAvailable(_))
+            ));
+
+            let queue = ctx.queue_builder().profiling(true).build();
+            let mut node = crate::EventNode::new([], true);
+            queue.write_buffer(&mut buf, 0, &host, Some(&mut node));
+            let event = node.take().unwrap();
+            event.wait();
+            assert_eq!(event.status(), EventStatus::Complete);
+            let queued = event.timestamp(ProfilingInfo::Queued).unwrap();
+            let start = event.timestamp(ProfilingInfo::Start).unwrap();
+            let end = event.timestamp(ProfilingInfo::End).unwrap();
+            assert!(queued <= start && start <= end);
+            assert_eq!(event.elapsed().unwrap().as_nanos(), (end - start) as u128);
+        }
+    }
+}
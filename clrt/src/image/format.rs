@@ -0,0 +1,82 @@
+use crate::bindings::{cl_channel_order, cl_channel_type, cl_image_format};
+
+cl_enum! {
+    /// 图像的通道顺序。
+    pub enum ChannelOrder: cl_channel_order {
+        R           = CL_R,
+        A           = CL_A,
+        Rg          = CL_RG,
+        Ra          = CL_RA,
+        Rgb         = CL_RGB,
+        Rgba        = CL_RGBA,
+        Bgra        = CL_BGRA,
+        Argb        = CL_ARGB,
+        Intensity   = CL_INTENSITY,
+        Luminance   = CL_LUMINANCE,
+        Rx          = CL_Rx,
+        Rgx         = CL_RGx,
+        Rgbx        = CL_RGBx,
+        Depth       = CL_DEPTH,
+        #[cfg(cl_2_0)]
+        Srgb        = CL_sRGB,
+        #[cfg(cl_2_0)]
+        Srgbx       = CL_sRGBx,
+        #[cfg(cl_2_0)]
+        Srgba       = CL_sRGBA,
+        #[cfg(cl_2_0)]
+        Sbgra       = CL_sBGRA,
+        #[cfg(cl_2_0)]
+        Abgr        = CL_ABGR,
+    }
+}
+
+cl_enum! {
+    /// 图像通道的数据类型。
+    pub enum ChannelType: cl_channel_type {
+        SnormInt8       = CL_SNORM_INT8,
+        SnormInt16      = CL_SNORM_INT16,
+        UnormInt8       = CL_UNORM_INT8,
+        UnormInt16      = CL_UNORM_INT16,
+        UnormShort565   = CL_UNORM_SHORT_565,
+        UnormShort555   = CL_UNORM_SHORT_555,
+        UnormInt101010  = CL_UNORM_INT_101010,
+        SignedInt8      = CL_SIGNED_INT8,
+        SignedInt16     = CL_SIGNED_INT16,
+        SignedInt32     = CL_SIGNED_INT32,
+        UnsignedInt8    = CL_UNSIGNED_INT8,
+        UnsignedInt16   = CL_UNSIGNED_INT16,
+        UnsignedInt32   = CL_UNSIGNED_INT32,
+        HalfFloat       = CL_HALF_FLOAT,
+        Float           = CL_FLOAT,
+    }
+}
+
+/// 图像格式描述符，对应 `cl_image_format`。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ImageFormat {
+    pub order: ChannelOrder,
+    pub data_type: ChannelType,
+}
+
+impl ImageFormat {
+    #[inline]
+    pub const fn new(order: ChannelOrder, data_type: ChannelType) -> Self {
+        Self { order, data_type }
+    }
+
+    #[inline]
+    pub fn from_raw(raw: cl_image_format) -> Option<Self> {
+        Some(Self {
+            order: ChannelOrder::from_raw(raw.image_channel_order)?,
+            data_type: ChannelType::from_raw(raw.image_channel_data_type)?,
+        })
+    }
+
+    #[inline]
+    pub fn as_raw(&self) -> cl_image_format {
+        cl_image_format {
+            image_channel_order: self.order.as_raw(),
+            image_channel_data_type: self.data_type.as_raw(),
+        }
+    }
+}
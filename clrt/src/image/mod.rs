@@ -0,0 +1,555 @@
+mod format;
+mod sampler;
+
+use crate::{
+    bindings::{
+        cl_image_desc, cl_mem, cl_mem_object_type, cl_uint, CL_MAP_READ, CL_MAP_WRITE,
+        CL_MAP_WRITE_INVALIDATE_REGION, CL_MEM_OBJECT_IMAGE1D, CL_MEM_OBJECT_IMAGE1D_ARRAY,
+        CL_MEM_OBJECT_IMAGE2D, CL_MEM_OBJECT_IMAGE2D_ARRAY, CL_MEM_OBJECT_IMAGE3D,
+    },
+    kernel::Argument,
+    node::{destruct, NodeParts},
+    ArgInfo, AsRaw, CommandQueue, Context, Error, EventNode, Kernel,
+};
+use std::{
+    ffi::c_void,
+    mem::forget,
+    ops::{Deref, DerefMut},
+    ptr::{null_mut, NonNull},
+    slice::{from_raw_parts, from_raw_parts_mut},
+};
+
+pub use format::{ChannelOrder, ChannelType, ImageFormat};
+pub use sampler::{AddressingMode, FilterMode, Sampler};
+
+/// 图像的维度和尺寸。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ImageShape {
+    D1 {
+        width: usize,
+    },
+    D2 {
+        width: usize,
+        height: usize,
+    },
+    D3 {
+        width: usize,
+        height: usize,
+        depth: usize,
+    },
+    D1Array {
+        width: usize,
+        array_size: usize,
+    },
+    D2Array {
+        width: usize,
+        height: usize,
+        array_size: usize,
+    },
+}
+
+impl ImageShape {
+    /// 覆盖整个图像的 `region`，用于各种图像命令。
+    #[inline]
+    pub fn region(&self) -> [usize; 3] {
+        match *self {
+            Self::D1 { width } => [width, 1, 1],
+            Self::D2 { width, height } => [width, height, 1],
+            Self::D3 {
+                width,
+                height,
+                depth,
+            } => [width, height, depth],
+            Self::D1Array { width, array_size } => [width, array_size, 1],
+            Self::D2Array {
+                width,
+                height,
+                array_size,
+            } => [width, height, array_size],
+        }
+    }
+
+    fn desc(&self) -> cl_image_desc {
+        let mut desc: cl_image_desc = unsafe { std::mem::zeroed() };
+        let (ty, [width, height, depth]): (cl_mem_object_type, _) = match *self {
+            Self::D1 { width } => (CL_MEM_OBJECT_IMAGE1D as _, [width, 0, 0]),
+            Self::D2 { width, height } => (CL_MEM_OBJECT_IMAGE2D as _, [width, height, 0]),
+            Self::D3 {
+                width,
+                height,
+                depth,
+            } => (CL_MEM_OBJECT_IMAGE3D as _, [width, height, depth]),
+            Self::D1Array { width, array_size } => {
+                desc.image_array_size = array_size;
+                (CL_MEM_OBJECT_IMAGE1D_ARRAY as _, [width, 0, 0])
+            }
+            Self::D2Array {
+                width,
+                height,
+                array_size,
+            } => {
+                desc.image_array_size = array_size;
+                (CL_MEM_OBJECT_IMAGE2D_ARRAY as _, [width, height, 0])
+            }
+        };
+        desc.image_type = ty;
+        desc.image_width = width;
+        desc.image_height = height;
+        desc.image_depth = depth;
+        desc
+    }
+}
+
+/// 基于 `clCreateImage` 的图像对象。
+pub struct Image {
+    raw: cl_mem,
+    shape: ImageShape,
+    format: ImageFormat,
+}
+
+impl Context {
+    #[inline]
+    pub fn image(&self, format: ImageFormat, shape: ImageShape) -> Image {
+        self.try_image(format, shape).unwrap()
+    }
+
+    pub fn try_image(&self, format: ImageFormat, shape: ImageShape) -> Result<Image, Error> {
+        let raw_format = format.as_raw();
+        let desc = shape.desc();
+        Ok(Image {
+            raw: try_cl!(err => clCreateImage(
+                self.as_raw(),
+                CL_MEM_READ_WRITE as _,
+                &raw_format,
+                &desc,
+                null_mut(),
+                &mut err
+            ))?,
+            shape,
+            format,
+        })
+    }
+}
+
+unsafe impl Send for Image {}
+unsafe impl Sync for Image {}
+
+impl Clone for Image {
+    fn clone(&self) -> Self {
+        cl!(clRetainMemObject(self.raw));
+        Self {
+            raw: self.raw,
+            shape: self.shape,
+            format: self.format,
+        }
+    }
+}
+
+impl Drop for Image {
+    fn drop(&mut self) {
+        cl!(clReleaseMemObject(self.raw))
+    }
+}
+
+impl AsRaw for Image {
+    type Raw = cl_mem;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.raw
+    }
+
+    #[inline]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn query(&self, key: cl_uint, val_size: usize, val: *mut c_void, size_ret: &mut usize) {
+        cl!(clGetImageInfo(self.as_raw(), key, val_size, val, size_ret))
+    }
+}
+
+impl Image {
+    #[inline]
+    pub fn shape(&self) -> ImageShape {
+        self.shape
+    }
+
+    #[inline]
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    /// 每个像素的字节数。
+    #[inline]
+    pub fn element_size(&self) -> usize {
+        use crate::bindings::CL_IMAGE_ELEMENT_SIZE;
+        self.query_value(CL_IMAGE_ELEMENT_SIZE)
+    }
+
+    /// 紧密排布时 `region` 区域的字节数。
+    #[inline]
+    fn region_size(&self, region: [usize; 3]) -> usize {
+        region.iter().product::<usize>() * self.element_size()
+    }
+}
+
+impl Argument for Image {
+    #[inline]
+    fn try_set_to(&self, kernel: &mut Kernel, index: usize) -> Result<(), Error> {
+        try_cl!(clSetKernelArg(
+            kernel.0,
+            index as _,
+            size_of::<cl_mem>(),
+            (&self.raw as *const cl_mem).cast()
+        ))
+    }
+
+    #[inline]
+    fn accepts(&self, info: &ArgInfo) -> bool {
+        info.type_name
+            == match self.shape {
+                ImageShape::D1 { .. } => "image1d_t",
+                ImageShape::D2 { .. } => "image2d_t",
+                ImageShape::D3 { .. } => "image3d_t",
+                ImageShape::D1Array { .. } => "image1d_array_t",
+                ImageShape::D2Array { .. } => "image2d_array_t",
+            }
+    }
+}
+
+/// 映射到主机的图像区域，`W` 表示是否可写。释放时自动解除映射。
+pub struct ImageMap<'a, const W: bool> {
+    queue: &'a CommandQueue,
+    img: &'a Image,
+    ptr: NonNull<u8>,
+    len: usize,
+    row_pitch: usize,
+    slice_pitch: usize,
+}
+
+impl<const W_: bool> Drop for ImageMap<'_, W_> {
+    fn drop(&mut self) {
+        let _ = self.unmap_(None);
+    }
+}
+
+impl<const W_: bool> ImageMap<'_, W_> {
+    /// 解除映射，与直接释放映射相同。
+    #[inline]
+    pub fn unmap(self) {
+        self.unmap_with(None)
+    }
+
+    /// 解除映射，等待 `event` 中的事件，并记录解除映射的事件。
+    pub fn unmap_with(self, event: Option<&mut EventNode>) {
+        self.unmap_(event).unwrap();
+        forget(self)
+    }
+
+    fn unmap_(&self, event: Option<&mut EventNode>) -> Result<(), Error> {
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        try_cl!(clEnqueueUnmapMemObject(
+            self.queue.as_raw(),
+            self.img.raw,
+            self.ptr.as_ptr().cast(),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+
+    #[inline]
+    pub fn row_pitch(&self) -> usize {
+        self.row_pitch
+    }
+
+    #[inline]
+    pub fn slice_pitch(&self) -> usize {
+        self.slice_pitch
+    }
+}
+
+impl<const W_: bool> Deref for ImageMap<'_, W_> {
+    type Target = [u8];
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for ImageMap<'_, true> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl CommandQueue {
+    pub fn read_image<T: Copy>(
+        &self,
+        dst: &mut [T],
+        src: &Image,
+        origin: [usize; 3],
+        region: [usize; 3],
+        event: Option<&mut EventNode>,
+    ) {
+        assert_eq!(size_of_val(dst), src.region_size(region));
+
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        cl!(clEnqueueReadImage(
+            self.as_raw(),
+            src.raw,
+            CL_FALSE,
+            origin.as_ptr(),
+            region.as_ptr(),
+            0,
+            0,
+            dst.as_mut_ptr().cast(),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+
+    pub fn write_image<T: Copy>(
+        &self,
+        dst: &mut Image,
+        origin: [usize; 3],
+        region: [usize; 3],
+        src: &[T],
+        event: Option<&mut EventNode>,
+    ) {
+        assert_eq!(size_of_val(src), dst.region_size(region));
+
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        cl!(clEnqueueWriteImage(
+            self.as_raw(),
+            dst.raw,
+            CL_FALSE,
+            origin.as_ptr(),
+            region.as_ptr(),
+            0,
+            0,
+            src.as_ptr().cast(),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+
+    pub fn copy_image(
+        &self,
+        dst: &mut Image,
+        dst_origin: [usize; 3],
+        src: &Image,
+        src_origin: [usize; 3],
+        region: [usize; 3],
+        event: Option<&mut EventNode>,
+    ) {
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        cl!(clEnqueueCopyImage(
+            self.as_raw(),
+            src.raw,
+            dst.raw,
+            src_origin.as_ptr(),
+            dst_origin.as_ptr(),
+            region.as_ptr(),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+
+    /// 以 `color` 填充图像区域。
+    ///
+    /// 按图像的通道类型，`T` 应为 `f32`、`i32` 或 `u32`。
+    pub fn fill_image<T: Copy>(
+        &self,
+        dst: &mut Image,
+        color: [T; 4],
+        origin: [usize; 3],
+        region: [usize; 3],
+        event: Option<&mut EventNode>,
+    ) {
+        assert_eq!(size_of::<T>(), 4);
+
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        cl!(clEnqueueFillImage(
+            self.as_raw(),
+            dst.raw,
+            color.as_ptr().cast(),
+            origin.as_ptr(),
+            region.as_ptr(),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+
+    pub fn map_image<'a>(
+        &'a self,
+        img: &'a Image,
+        origin: [usize; 3],
+        region: [usize; 3],
+        event: Option<&mut EventNode>,
+    ) -> ImageMap<'a, false> {
+        self.map_image_(img, origin, region, CL_MAP_READ, event)
+    }
+
+    pub fn map_image_mut<'a>(
+        &'a self,
+        img: &'a mut Image,
+        origin: [usize; 3],
+        region: [usize; 3],
+        readable: bool,
+        event: Option<&mut EventNode>,
+    ) -> ImageMap<'a, true> {
+        let flags = if readable {
+            CL_MAP_READ | CL_MAP_WRITE
+        } else {
+            CL_MAP_WRITE_INVALIDATE_REGION
+        };
+        self.map_image_(img, origin, region, flags, event)
+    }
+
+    fn map_image_<'a, const W: bool>(
+        &'a self,
+        img: &'a Image,
+        origin: [usize; 3],
+        region: [usize; 3],
+        flags: u32,
+        event: Option<&mut EventNode>,
+    ) -> ImageMap<'a, W> {
+        let mut row_pitch = 0;
+        let mut slice_pitch = 0;
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        let ptr = cl!(err => clEnqueueMapImage(
+            self.as_raw(),
+            img.raw,
+            CL_TRUE,
+            flags as _,
+            origin.as_ptr(),
+            region.as_ptr(),
+            &mut row_pitch,
+            &mut slice_pitch,
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            &mut err,
+        ));
+
+        // 一维图像数组的各层以 slice pitch 为间隔
+        let [w, h, d] = region;
+        let (row, slice) = match img.shape {
+            ImageShape::D1Array { .. } => (slice_pitch, 0),
+            _ => (row_pitch, slice_pitch),
+        };
+        let len = if region.contains(&0) {
+            0
+        } else {
+            (d - 1) * slice + (h - 1) * row + w * img.element_size()
+        };
+        ImageMap {
+            queue: self,
+            img,
+            ptr: NonNull::new(ptr.cast()).unwrap_or(NonNull::dangling()),
+            len,
+            row_pitch,
+            slice_pitch,
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use std::ffi::CString;
+
+    const PROGRAM_SOURCE: &str = r#"
+kernel void sample(read_only image2d_t src, sampler_t sampler, global float* dst) {
+    int x = get_global_id(0);
+    int y = get_global_id(1);
+    dst[y * get_global_size(0) + x] = read_imagef(src, sampler, (int2)(x, y)).x;
+}"#;
+
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let context = device.context();
+            let queue = context.queue();
+
+            let format = ImageFormat::new(ChannelOrder::R, ChannelType::Float);
+            let shape = ImageShape::D2 {
+                width: 16,
+                height: 8,
+            };
+            let region = shape.region();
+            let mut a = context.image(format, shape);
+            let mut b = context.image(format, shape);
+            assert_eq!(a.element_size(), size_of::<f32>());
+
+            let host = (0..16 * 8).map(|i| i as f32).collect::<Vec<_>>();
+            queue.write_image(&mut a, [0; 3], region, &host, None);
+            queue.copy_image(&mut b, [0; 3], &a, [0; 3], region, None);
+
+            let mut ans = vec![0f32; host.len()];
+            queue.read_image(&mut ans, &b, [0; 3], region, None);
+            queue.finish();
+            assert_eq!(ans, host);
+
+            queue.fill_image(&mut b, [1f32, 0., 0., 0.], [0; 3], region, None);
+            let map = queue.map_image(&b, [0; 3], region, None);
+            for y in 0..8 {
+                let row = &map[y * map.row_pitch()..][..16 * size_of::<f32>()];
+                assert!(row
+                    .chunks(4)
+                    .all(|x| f32::from_ne_bytes(x.try_into().unwrap()) == 1.));
+            }
+            map.unmap();
+
+            let sampler = context.sampler(false, AddressingMode::ClampToEdge, FilterMode::Nearest);
+            assert_eq!(sampler.addressing_mode(), AddressingMode::ClampToEdge);
+            assert_eq!(sampler.filter_mode(), FilterMode::Nearest);
+            assert!(!sampler.normalized_coords());
+
+            let program = context
+                .build_from_source(PROGRAM_SOURCE, CString::default())
+                .unwrap();
+            let mut kernel = program.get_kernel(c"sample").unwrap();
+            let dst = context.buffer::<f32>(host.len());
+            kernel
+                .set_arg(0, &a)
+                .set_arg(1, &sampler)
+                .set_arg(2, &dst)
+                .launch(&[0, 0], &[16, 8], &[1, 1], &queue, None);
+            queue.read_buffer(&mut ans, &dst, 0, None);
+            queue.finish();
+            assert_eq!(ans, host);
+        }
+    }
+}
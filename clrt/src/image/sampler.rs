@@ -0,0 +1,151 @@
+use crate::{
+    bindings::{
+        cl_addressing_mode, cl_bool, cl_filter_mode, cl_sampler, cl_uint,
+        CL_SAMPLER_ADDRESSING_MODE, CL_SAMPLER_FILTER_MODE, CL_SAMPLER_NORMALIZED_COORDS,
+    },
+    kernel::Argument,
+    ArgInfo, AsRaw, Context, Error, Kernel,
+};
+use std::ffi::c_void;
+
+cl_enum! {
+    /// 采样器处理越界坐标的方式。
+    pub enum AddressingMode: cl_addressing_mode {
+        None           = CL_ADDRESS_NONE,
+        ClampToEdge    = CL_ADDRESS_CLAMP_TO_EDGE,
+        Clamp          = CL_ADDRESS_CLAMP,
+        Repeat         = CL_ADDRESS_REPEAT,
+        MirroredRepeat = CL_ADDRESS_MIRRORED_REPEAT,
+    }
+}
+
+cl_enum! {
+    /// 采样器的过滤方式。
+    pub enum FilterMode: cl_filter_mode {
+        Nearest = CL_FILTER_NEAREST,
+        Linear  = CL_FILTER_LINEAR,
+    }
+}
+
+#[repr(transparent)]
+pub struct Sampler(cl_sampler);
+
+impl Context {
+    #[inline]
+    pub fn sampler(
+        &self,
+        normalized_coords: bool,
+        addressing: AddressingMode,
+        filter: FilterMode,
+    ) -> Sampler {
+        self.try_sampler(normalized_coords, addressing, filter)
+            .unwrap()
+    }
+
+    pub fn try_sampler(
+        &self,
+        normalized_coords: bool,
+        addressing: AddressingMode,
+        filter: FilterMode,
+    ) -> Result<Sampler, Error> {
+        #[cfg(cl_2_0)]
+        if self
+            .devices()
+            .iter()
+            .all(|d| d.require(2, 0, "clCreateSamplerWithProperties").is_ok())
+        {
+            use crate::bindings::cl_sampler_properties;
+            let properties: [cl_sampler_properties; 7] = [
+                CL_SAMPLER_NORMALIZED_COORDS as _,
+                normalized_coords as _,
+                CL_SAMPLER_ADDRESSING_MODE as _,
+                addressing.as_raw() as _,
+                CL_SAMPLER_FILTER_MODE as _,
+                filter.as_raw() as _,
+                0,
+            ];
+            return Ok(Sampler(try_cl!(err => clCreateSamplerWithProperties(
+                self.as_raw(),
+                properties.as_ptr(),
+                &mut err
+            ))?));
+        }
+        Ok(Sampler(try_cl!(err => clCreateSampler(
+            self.as_raw(),
+            normalized_coords as cl_bool,
+            addressing.as_raw(),
+            filter.as_raw(),
+            &mut err
+        ))?))
+    }
+}
+
+unsafe impl Send for Sampler {}
+unsafe impl Sync for Sampler {}
+
+impl Clone for Sampler {
+    fn clone(&self) -> Self {
+        cl!(clRetainSampler(self.0));
+        Self(self.0)
+    }
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        cl!(clReleaseSampler(self.0))
+    }
+}
+
+impl AsRaw for Sampler {
+    type Raw = cl_sampler;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0
+    }
+
+    #[inline]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn query(&self, key: cl_uint, val_size: usize, val: *mut c_void, size_ret: &mut usize) {
+        cl!(clGetSamplerInfo(
+            self.as_raw(),
+            key,
+            val_size,
+            val,
+            size_ret
+        ))
+    }
+}
+
+impl Sampler {
+    #[inline]
+    pub fn normalized_coords(&self) -> bool {
+        self.query_value::<cl_bool>(CL_SAMPLER_NORMALIZED_COORDS) != 0
+    }
+
+    #[inline]
+    pub fn addressing_mode(&self) -> AddressingMode {
+        AddressingMode::from_raw(self.query_value(CL_SAMPLER_ADDRESSING_MODE)).unwrap()
+    }
+
+    #[inline]
+    pub fn filter_mode(&self) -> FilterMode {
+        FilterMode::from_raw(self.query_value(CL_SAMPLER_FILTER_MODE)).unwrap()
+    }
+}
+
+impl Argument for Sampler {
+    #[inline]
+    fn try_set_to(&self, kernel: &mut Kernel, index: usize) -> Result<(), Error> {
+        try_cl!(clSetKernelArg(
+            kernel.0,
+            index as _,
+            size_of::<cl_sampler>(),
+            (&self.0 as *const cl_sampler).cast()
+        ))
+    }
+
+    #[inline]
+    fn accepts(&self, info: &ArgInfo) -> bool {
+        info.type_name == "sampler_t"
+    }
+}
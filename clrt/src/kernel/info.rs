@@ -0,0 +1,252 @@
+use super::{Argument, Kernel};
+use crate::{
+    bindings::{
+        cl_kernel_arg_access_qualifier, cl_kernel_arg_address_qualifier, cl_uint,
+        CL_KERNEL_ARG_ACCESS_QUALIFIER, CL_KERNEL_ARG_ADDRESS_QUALIFIER, CL_KERNEL_ARG_NAME,
+        CL_KERNEL_ARG_TYPE_NAME,
+    },
+    AsRaw, Error,
+};
+use std::{any::type_name, ffi::c_void, fmt, ptr::null_mut};
+
+cl_enum! {
+    /// 核函数参数的地址空间。
+    pub enum AddressQualifier: cl_kernel_arg_address_qualifier {
+        Global   = CL_KERNEL_ARG_ADDRESS_GLOBAL,
+        Local    = CL_KERNEL_ARG_ADDRESS_LOCAL,
+        Constant = CL_KERNEL_ARG_ADDRESS_CONSTANT,
+        Private  = CL_KERNEL_ARG_ADDRESS_PRIVATE,
+    }
+}
+
+cl_enum! {
+    /// 图像和管道参数的访问限定符。
+    pub enum AccessQualifier: cl_kernel_arg_access_qualifier {
+        ReadOnly  = CL_KERNEL_ARG_ACCESS_READ_ONLY,
+        WriteOnly = CL_KERNEL_ARG_ACCESS_WRITE_ONLY,
+        ReadWrite = CL_KERNEL_ARG_ACCESS_READ_WRITE,
+        None      = CL_KERNEL_ARG_ACCESS_NONE,
+    }
+}
+
+/// 核函数参数的元信息，需要程序以 `-cl-kernel-arg-info` 选项构建。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ArgInfo {
+    pub name: String,
+    pub type_name: String,
+    pub address: AddressQualifier,
+    pub access: AccessQualifier,
+}
+
+impl ArgInfo {
+    /// 参数是否是指针。
+    #[inline]
+    pub fn is_pointer(&self) -> bool {
+        self.type_name.ends_with('*')
+    }
+
+    /// 参数是否是 OpenCL™ C 的内置标量或向量类型。
+    pub fn is_builtin_value(&self) -> bool {
+        const SCALARS: &[&str] = &[
+            "bool",
+            "char",
+            "uchar",
+            "short",
+            "ushort",
+            "int",
+            "uint",
+            "long",
+            "ulong",
+            "half",
+            "float",
+            "double",
+            "size_t",
+            "ptrdiff_t",
+            "intptr_t",
+            "uintptr_t",
+        ];
+        let base = self
+            .type_name
+            .trim_end_matches(|c: char| c.is_ascii_digit());
+        SCALARS.contains(&base)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ArgError {
+    /// 参数序号超出核函数的参数数量。
+    IndexOutOfRange {
+        index: usize,
+        num_args: usize,
+    },
+    /// 核函数没有这个名字的参数。
+    UnknownName(String),
+    /// Rust 类型与核函数声明的参数类型不匹配。
+    TypeMismatch {
+        index: usize,
+        info: ArgInfo,
+        rust_type: &'static str,
+    },
+    Others(Error),
+}
+
+impl From<Error> for ArgError {
+    #[inline]
+    fn from(value: Error) -> Self {
+        Self::Others(value)
+    }
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::IndexOutOfRange { index, num_args } => {
+                write!(f, "argument index {index} out of range, kernel has {num_args} arguments")
+            }
+            Self::UnknownName(name) => write!(f, "kernel has no argument named `{name}`"),
+            Self::TypeMismatch {
+                index,
+                info,
+                rust_type,
+            } => write!(
+                f,
+                "argument {index} `{}` is declared as `{:?} {}`, which does not accept `{rust_type}`",
+                info.name, info.address, info.type_name,
+            ),
+            Self::Others(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ArgError {}
+
+impl Kernel {
+    #[inline]
+    pub fn num_args(&self) -> usize {
+        use crate::bindings::CL_KERNEL_NUM_ARGS;
+        self.query_value::<cl_uint>(CL_KERNEL_NUM_ARGS) as _
+    }
+
+    pub fn arg_info(&self, index: usize) -> Result<ArgInfo, Error> {
+        let string = |key| -> Result<String, Error> {
+            let mut size = 0;
+            self.arg_info_raw(index, key, 0, null_mut(), &mut size)?;
+            let mut ans = vec![0u8; size];
+            self.arg_info_raw(index, key, size, ans.as_mut_ptr().cast(), &mut size)?;
+            assert_eq!(ans.pop(), Some(0));
+            Ok(String::from_utf8(ans).unwrap())
+        };
+        let value = |key| -> Result<cl_uint, Error> {
+            let mut ans: cl_uint = 0;
+            let mut size = 0;
+            self.arg_info_raw(
+                index,
+                key,
+                size_of_val(&ans),
+                (&raw mut ans).cast(),
+                &mut size,
+            )?;
+            assert_eq!(size, size_of_val(&ans));
+            Ok(ans)
+        };
+
+        Ok(ArgInfo {
+            name: string(CL_KERNEL_ARG_NAME)?,
+            type_name: string(CL_KERNEL_ARG_TYPE_NAME)?,
+            address: AddressQualifier::from_raw(value(CL_KERNEL_ARG_ADDRESS_QUALIFIER)?).unwrap(),
+            access: AccessQualifier::from_raw(value(CL_KERNEL_ARG_ACCESS_QUALIFIER)?).unwrap(),
+        })
+    }
+
+    pub fn args(&self) -> Result<Vec<ArgInfo>, Error> {
+        (0..self.num_args()).map(|i| self.arg_info(i)).collect()
+    }
+
+    /// 检查 `value` 的类型与核函数声明的参数类型匹配后再设置参数。
+    pub fn set_arg_checked<T: Argument>(
+        &mut self,
+        index: usize,
+        value: T,
+    ) -> Result<&mut Self, ArgError> {
+        let num_args = self.num_args();
+        if index >= num_args {
+            return Err(ArgError::IndexOutOfRange { index, num_args });
+        }
+        let info = self.arg_info(index)?;
+        if !value.accepts(&info) {
+            return Err(ArgError::TypeMismatch {
+                index,
+                info,
+                rust_type: type_name::<T>(),
+            });
+        }
+        Ok(self.try_set_arg(index, value)?)
+    }
+
+    fn arg_info_raw(
+        &self,
+        index: usize,
+        key: cl_uint,
+        val_size: usize,
+        val: *mut c_void,
+        size_ret: &mut usize,
+    ) -> Result<(), Error> {
+        try_cl!(clGetKernelArgInfo(
+            self.as_raw(),
+            index as _,
+            key,
+            val_size,
+            val,
+            size_ret
+        ))
+    }
+}
+
+#[test]
+fn test() {
+    const PROGRAM_SOURCE: &str = r#"
+kernel void saxpy_float (global float* z,
+    global float const* x,
+    float a)
+{
+    const size_t i = get_global_id(0);
+    z[i] = a*x[i] + z[i];
+}"#;
+
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let context = device.context();
+            let program = context.build_from_source(PROGRAM_SOURCE, c"").unwrap();
+            let mut kernel = program.get_kernel(c"saxpy_float").unwrap();
+
+            let args = kernel.args().unwrap();
+            assert_eq!(kernel.num_args(), 3);
+            assert_eq!(args[0].name, "z");
+            assert_eq!(args[0].type_name, "float*");
+            assert_eq!(args[0].address, AddressQualifier::Global);
+            assert_eq!(args[2].name, "a");
+            assert_eq!(args[2].type_name, "float");
+            assert_eq!(args[2].address, AddressQualifier::Private);
+            assert_eq!(args[2].access, AccessQualifier::None);
+
+            let z = context.buffer::<f32>(16);
+            assert!(kernel.set_arg_checked(0, &z).is_ok());
+            assert!(kernel.set_arg_checked(2, 2.0f32).is_ok());
+            assert!(matches!(
+                kernel.set_arg_checked(2, 2.0f64),
+                Err(ArgError::TypeMismatch { index: 2, .. })
+            ));
+            assert!(matches!(
+                kernel.set_arg_checked(1, 2.0f32),
+                Err(ArgError::TypeMismatch { index: 1, .. })
+            ));
+            assert!(matches!(
+                kernel.set_arg_checked(3, 2.0f32),
+                Err(ArgError::IndexOutOfRange {
+                    index: 3,
+                    num_args: 3
+                })
+            ));
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use super::{ArgError, ArgInfo, Argument, Kernel};
+use crate::{
+    node::{destruct, NodeParts},
+    AsRaw, CommandQueue, Error, Event, EventNode,
+};
+use smallvec::SmallVec;
+use std::{fmt, ptr::null};
+
+type Dims = SmallVec<[usize; 3]>;
+
+/// 核函数启动的构建器，由 [`Kernel::launcher`] 创建。
+///
+/// 设置参数时发生的第一个错误会保留到 [`Launch::try_enqueue`] 返回。
+pub struct Launch<'a> {
+    kernel: &'a mut Kernel,
+    queue: &'a CommandQueue,
+    args: Option<Vec<ArgInfo>>,
+    global: Dims,
+    local: Option<Dims>,
+    offset: Option<Dims>,
+    wait: Vec<Event>,
+    record: bool,
+    err: Option<ArgError>,
+}
+
+#[derive(Clone, Debug)]
+pub enum LaunchError {
+    Arg(ArgError),
+    /// 工作维度为 0 或超出设备支持的最大维度。
+    WorkDim {
+        work_dim: usize,
+        max: usize,
+    },
+    /// 局部大小或偏移的维度与全局大小不一致。
+    DimMismatch {
+        what: &'static str,
+        len: usize,
+        work_dim: usize,
+    },
+    /// 工作组大小超出核函数在设备上的最大工作组大小。
+    GroupSize {
+        size: usize,
+        max: usize,
+    },
+    Others(Error),
+}
+
+impl From<ArgError> for LaunchError {
+    #[inline]
+    fn from(value: ArgError) -> Self {
+        Self::Arg(value)
+    }
+}
+
+impl From<Error> for LaunchError {
+    #[inline]
+    fn from(value: Error) -> Self {
+        Self::Others(value)
+    }
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Arg(e) => write!(f, "{e}"),
+            Self::WorkDim { work_dim, max } => {
+                write!(f, "work dim {work_dim} is not in range 1..={max}")
+            }
+            Self::DimMismatch {
+                what,
+                len,
+                work_dim,
+            } => write!(f, "{what} has {len} dims, but work dim is {work_dim}"),
+            Self::GroupSize { size, max } => {
+                write!(
+                    f,
+                    "work group size {size} exceeds kernel max group size {max}"
+                )
+            }
+            Self::Others(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for LaunchError {}
+
+impl Kernel {
+    /// 在 `queue` 上启动核函数。
+    #[inline]
+    pub fn launcher<'a>(&'a mut self, queue: &'a CommandQueue) -> Launch<'a> {
+        Launch {
+            kernel: self,
+            queue,
+            args: None,
+            global: Dims::new(),
+            local: None,
+            offset: None,
+            wait: Vec::new(),
+            record: false,
+            err: None,
+        }
+    }
+}
+
+impl Launch<'_> {
+    /// 按位置设置参数，不做类型检查。
+    #[inline]
+    pub fn arg(mut self, index: usize, value: impl Argument) -> Self {
+        if self.err.is_none() {
+            if let Err(e) = self.kernel.try_set_arg(index, value) {
+                self.err = Some(e.into())
+            }
+        }
+        self
+    }
+
+    /// 按名字设置参数，需要程序以 `-cl-kernel-arg-info` 选项构建。
+    pub fn arg_named<T: Argument>(mut self, name: &str, value: T) -> Self {
+        if self.err.is_none() {
+            if let Err(e) = self.set_named(name, value) {
+                self.err = Some(e)
+            }
+        }
+        self
+    }
+
+    fn set_named<T: Argument>(&mut self, name: &str, value: T) -> Result<(), ArgError> {
+        let args = match &mut self.args {
+            Some(args) => args,
+            None => self.args.insert(self.kernel.args()?),
+        };
+        let Some(index) = args.iter().position(|info| info.name == name) else {
+            return Err(ArgError::UnknownName(name.into()));
+        };
+        self.kernel.set_arg_checked(index, value)?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn global(mut self, size: &[usize]) -> Self {
+        self.global = size.into();
+        self
+    }
+
+    /// 设置工作组大小。不设置时由驱动选择。
+    #[inline]
+    pub fn local(mut self, size: &[usize]) -> Self {
+        self.local = Some(size.into());
+        self
+    }
+
+    /// 设置全局偏移。不设置时为 0。
+    #[inline]
+    pub fn offset(mut self, offset: &[usize]) -> Self {
+        self.offset = Some(offset.into());
+        self
+    }
+
+    /// 等待 `event` 完成后再启动。
+    #[inline]
+    pub fn wait(mut self, event: Event) -> Self {
+        self.wait.push(event);
+        self
+    }
+
+    #[inline]
+    pub fn wait_all(mut self, events: impl IntoIterator<Item = Event>) -> Self {
+        self.wait.extend(events);
+        self
+    }
+
+    /// 记录启动的事件，由 [`Launch::enqueue`] 返回。
+    #[inline]
+    pub fn record(mut self) -> Self {
+        self.record = true;
+        self
+    }
+
+    #[inline]
+    pub fn enqueue(self) -> Option<Event> {
+        self.try_enqueue().unwrap()
+    }
+
+    pub fn try_enqueue(self) -> Result<Option<Event>, LaunchError> {
+        if let Some(e) = self.err {
+            return Err(e.into());
+        }
+
+        let work_dim = self.global.len();
+        let device = self.queue.device();
+        let max = device.max_work_dim();
+        if !(1..=max).contains(&work_dim) {
+            return Err(LaunchError::WorkDim { work_dim, max });
+        }
+        let check = |what, dims: &Option<Dims>| match dims {
+            Some(dims) if dims.len() != work_dim => Err(LaunchError::DimMismatch {
+                what,
+                len: dims.len(),
+                work_dim,
+            }),
+            _ => Ok(()),
+        };
+        check("offset", &self.offset)?;
+        check("local size", &self.local)?;
+        if let Some(local) = &self.local {
+            let size = local.iter().product();
+            let max = self.kernel.max_group_size_on(&device);
+            if size > max {
+                return Err(LaunchError::GroupSize { size, max });
+            }
+        }
+
+        let mut node = EventNode::new(self.wait, self.record);
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(Some(&mut node));
+        try_cl!(clEnqueueNDRangeKernel(
+            self.queue.as_raw(),
+            self.kernel.0,
+            work_dim as _,
+            self.offset.as_ref().map_or(null(), |d| d.as_ptr()),
+            self.global.as_ptr(),
+            self.local.as_ref().map_or(null(), |d| d.as_ptr()),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))?;
+        Ok(node.take())
+    }
+}
+
+#[test]
+fn test() {
+    const PROGRAM_SOURCE: &str = r#"
+kernel void axpy(global uint* y, uint a, global uint const* x) {
+    const size_t i = get_global_id(0);
+    y[i] += a * x[i];
+}"#;
+
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let context = device.context();
+            let queue = context.queue();
+            let program = context.build_from_source(PROGRAM_SOURCE, c"").unwrap();
+            let mut kernel = program.get_kernel(c"axpy").unwrap();
+
+            let n = 64;
+            let host = (0..n as u32).collect::<Vec<_>>();
+            let mut x = context.buffer::<u32>(n);
+            let mut y = context.buffer::<u32>(n);
+            queue.write_buffer(&mut x, 0, &host, None);
+            queue.write_buffer(&mut y, 0, &host, None);
+
+            let event = kernel
+                .launcher(&queue)
+                .arg_named("y", &y)
+                .arg_named("x", &x)
+                .arg(1, 2u32)
+                .global(&[n])
+                .record()
+                .enqueue()
+                .unwrap();
+            let _ = kernel
+                .launcher(&queue)
+                .global(&[n / 2])
+                .local(&[1])
+                .offset(&[n / 2])
+                .wait(event)
+                .enqueue();
+
+            let mut ans = vec![0u32; n];
+            queue.read_buffer(&mut ans, &y, 0, None);
+            queue.finish();
+            for (i, &y) in ans.iter().enumerate() {
+                let times = if i < n / 2 { 3 } else { 5 };
+                assert_eq!(y, times * i as u32)
+            }
+
+            let err = |launch: Launch| launch.try_enqueue().err().unwrap();
+            assert!(matches!(
+                err(kernel.launcher(&queue).arg_named("z", &y).global(&[n])),
+                LaunchError::Arg(ArgError::UnknownName(_))
+            ));
+            assert!(matches!(
+                err(kernel.launcher(&queue).arg_named("a", 2.0f32).global(&[n])),
+                LaunchError::Arg(ArgError::TypeMismatch { index: 1, .. })
+            ));
+            assert!(matches!(
+                err(kernel
+                    .launcher(&queue)
+                    .arg(3, 2u32)
+                    .arg_named("z", &y)
+                    .global(&[n])),
+                LaunchError::Arg(ArgError::Others(_))
+            ));
+            assert!(matches!(
+                err(kernel.launcher(&queue)),
+                LaunchError::WorkDim { work_dim: 0, .. }
+            ));
+            assert!(matches!(
+                err(kernel.launcher(&queue).global(&[n]).local(&[1, 1])),
+                LaunchError::DimMismatch {
+                    what: "local size",
+                    ..
+                }
+            ));
+            let max = kernel.max_group_size_on(&device);
+            assert!(matches!(
+                err(kernel.launcher(&queue).global(&[n]).local(&[max + 1])),
+                LaunchError::GroupSize { .. }
+            ));
+            assert_eq!(
+                kernel.try_launch(&[0], &[n], &[1, 1], &queue, None),
+                Err(Error::InvalidWorkDimension("Kernel::try_launch"))
+            );
+        }
+    }
+}
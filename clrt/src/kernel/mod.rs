@@ -0,0 +1,288 @@
+﻿mod info;
+mod launch;
+
+use crate::{
+    bindings::{cl_device_id, cl_kernel, cl_uint},
+    node::{destruct, NodeParts},
+    AsRaw, CommandQueue, Device, Error, EventNode,
+};
+use half::{bf16, f16};
+use std::{ffi::c_void, ptr::null_mut};
+
+pub use info::{AccessQualifier, AddressQualifier, ArgError, ArgInfo};
+pub use launch::{Launch, LaunchError};
+
+#[repr(transparent)]
+pub struct Kernel(pub(crate) cl_kernel);
+
+impl Drop for Kernel {
+    fn drop(&mut self) {
+        cl!(clReleaseKernel(self.0))
+    }
+}
+
+impl AsRaw for Kernel {
+    type Raw = cl_kernel;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0
+    }
+
+    #[inline]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn query(&self, key: cl_uint, val_size: usize, val: *mut c_void, size_ret: &mut usize) {
+        cl!(clGetKernelInfo(self.as_raw(), key, val_size, val, size_ret))
+    }
+}
+
+impl Kernel {
+    #[inline]
+    pub fn name(&self) -> String {
+        use crate::bindings::CL_KERNEL_FUNCTION_NAME;
+        self.query_string(CL_KERNEL_FUNCTION_NAME)
+    }
+
+    /// 核函数的最大工作组大小。核函数关联多个设备时应使用 [`Kernel::max_group_size_on`]。
+    #[inline]
+    pub fn max_group_size(&self) -> usize {
+        self.max_group_size_raw(null_mut())
+    }
+
+    /// 核函数在 `device` 上的最大工作组大小。
+    #[inline]
+    pub fn max_group_size_on(&self, device: &Device) -> usize {
+        self.max_group_size_raw(unsafe { device.as_raw() })
+    }
+
+    fn max_group_size_raw(&self, device: cl_device_id) -> usize {
+        let mut ans = 0usize;
+        let mut size = 0;
+        cl!(clGetKernelWorkGroupInfo(
+            self.0,
+            device,
+            CL_KERNEL_WORK_GROUP_SIZE,
+            size_of_val(&ans),
+            (&raw mut ans).cast(),
+            &mut size
+        ));
+        assert_eq!(size, size_of_val(&ans));
+        ans
+    }
+
+    #[inline]
+    pub fn set_arg(&mut self, index: usize, value: impl Argument) -> &mut Self {
+        self.try_set_arg(index, value).unwrap()
+    }
+
+    #[inline]
+    pub fn try_set_arg(&mut self, index: usize, value: impl Argument) -> Result<&mut Self, Error> {
+        value.try_set_to(self, index)?;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn launch(
+        &self,
+        global_work_offset: &[usize],
+        global_work_size: &[usize],
+        local_work_size: &[usize],
+        queue: &CommandQueue,
+        event: Option<&mut EventNode>,
+    ) {
+        self.try_launch(
+            global_work_offset,
+            global_work_size,
+            local_work_size,
+            queue,
+            event,
+        )
+        .unwrap()
+    }
+
+    /// 三个工作大小的维度不一致时不会调用 OpenCL™，直接返回 [`Error::InvalidWorkDimension`]。
+    pub fn try_launch(
+        &self,
+        global_work_offset: &[usize],
+        global_work_size: &[usize],
+        local_work_size: &[usize],
+        queue: &CommandQueue,
+        event: Option<&mut EventNode>,
+    ) -> Result<(), Error> {
+        let work_dim = local_work_size.len();
+        if global_work_offset.len() != work_dim || global_work_size.len() != work_dim {
+            return Err(Error::InvalidWorkDimension("Kernel::try_launch"));
+        }
+
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        try_cl!(clEnqueueNDRangeKernel(
+            queue.as_raw(),
+            self.0,
+            work_dim as _,
+            global_work_offset.as_ptr(),
+            global_work_size.as_ptr(),
+            local_work_size.as_ptr(),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+}
+
+pub trait Argument {
+    fn try_set_to(&self, kernel: &mut Kernel, index: usize) -> Result<(), Error>;
+
+    #[inline]
+    fn set_to(&self, kernel: &mut Kernel, index: usize) {
+        self.try_set_to(kernel, index).unwrap()
+    }
+
+    /// 判断参数能否传给 `info` 描述的核函数参数，用于 [`Kernel::set_arg_checked`]。
+    ///
+    /// 无法判断时应返回 `true`。
+    #[inline]
+    fn accepts(&self, info: &ArgInfo) -> bool {
+        let _ = info;
+        true
+    }
+}
+
+impl<T: Argument> Argument for &T {
+    fn try_set_to(&self, kernel: &mut Kernel, index: usize) -> Result<(), Error> {
+        T::try_set_to(*self, kernel, index)
+    }
+
+    #[inline]
+    fn accepts(&self, info: &ArgInfo) -> bool {
+        T::accepts(*self, info)
+    }
+}
+
+macro_rules! impl_for_num {
+    ($($ty:ty: [$($name:literal),*])+) => {
+        $(
+            impl Argument for $ty {
+                #[inline]
+                fn try_set_to(&self, kernel: &mut Kernel, index: usize) -> Result<(), Error> {
+                     try_cl!(clSetKernelArg(kernel.0, index as _, size_of::<Self>(), (self as *const Self).cast()))
+                }
+
+                #[inline]
+                fn accepts(&self, info: &ArgInfo) -> bool {
+                    // 类型名不是内置类型时（如 typedef）无法判断，只检查是否传值
+                    info.address == AddressQualifier::Private
+                        && !info.is_pointer()
+                        && (!info.is_builtin_value() || [$($name),*].contains(&info.type_name.as_str()))
+                }
+            }
+        )+
+    };
+}
+
+impl_for_num! {
+    u8   : ["uchar"]
+    i8   : ["char"]
+    u16  : ["ushort"]
+    i16  : ["short"]
+    f16  : ["half"]
+    bf16 : ["ushort"]
+    u32  : ["uint"]
+    i32  : ["int"]
+    f32  : ["float"]
+    u64  : ["ulong"]
+    i64  : ["long"]
+    f64  : ["double"]
+    u128 : []
+    i128 : []
+    usize: ["ulong", "size_t", "uintptr_t"]
+    isize: ["long", "ptrdiff_t", "intptr_t"]
+}
+
+/// 判断 `info` 是否是全局或常量内存中的指针参数。
+#[inline]
+pub(crate) fn accepts_global_pointer(info: &ArgInfo) -> bool {
+    info.is_pointer()
+        && matches!(
+            info.address,
+            AddressQualifier::Global | AddressQualifier::Constant
+        )
+}
+
+#[cfg(cl_2_0)]
+impl<T> Argument for *const crate::SvmElem<T> {
+    #[inline]
+    fn try_set_to(&self, kernel: &mut Kernel, index: usize) -> Result<(), Error> {
+        try_cl!(clSetKernelArgSVMPointer(kernel.0, index as _, self.cast()))
+    }
+
+    #[inline]
+    fn accepts(&self, info: &ArgInfo) -> bool {
+        accepts_global_pointer(info)
+    }
+}
+
+#[cfg(cl_2_0)]
+impl<T> Argument for *mut crate::SvmElem<T> {
+    #[inline]
+    fn try_set_to(&self, kernel: &mut Kernel, index: usize) -> Result<(), Error> {
+        try_cl!(clSetKernelArgSVMPointer(kernel.0, index as _, self.cast()))
+    }
+
+    #[inline]
+    fn accepts(&self, info: &ArgInfo) -> bool {
+        accepts_global_pointer(info)
+    }
+}
+
+#[cfg(cl_2_0)]
+impl Kernel {
+    /// 把主机内存 `mem` 作为全局指针参数，需要核函数所在上下文的所有设备支持细粒度系统 SVM。
+    ///
+    /// # Safety
+    ///
+    /// 使用这个参数的核函数执行完成前，`mem` 必须保持有效，并且主机不能访问。
+    #[inline]
+    pub unsafe fn set_arg_host<T: crate::SvmPod>(
+        &mut self,
+        index: usize,
+        mem: &mut [T],
+    ) -> &mut Self {
+        unsafe { self.try_set_arg_host(index, mem) }.unwrap()
+    }
+
+    /// 同 [`Kernel::set_arg_host`]，上下文中有设备不支持细粒度系统 SVM 时返回 [`Error::Unsupported`]。
+    ///
+    /// # Safety
+    ///
+    /// 同 [`Kernel::set_arg_host`]。
+    pub unsafe fn try_set_arg_host<T: crate::SvmPod>(
+        &mut self,
+        index: usize,
+        mem: &mut [T],
+    ) -> Result<&mut Self, Error> {
+        if !self.fine_grain_system_svm() {
+            return Err(Error::Unsupported("clSetKernelArgSVMPointer"));
+        }
+        try_cl!(clSetKernelArgSVMPointer(
+            self.0,
+            index as _,
+            mem.as_mut_ptr().cast()
+        ))?;
+        Ok(self)
+    }
+
+    fn fine_grain_system_svm(&self) -> bool {
+        use crate::{bindings::CL_KERNEL_CONTEXT, Context};
+
+        let raw = self.query_value(CL_KERNEL_CONTEXT);
+        cl!(clRetainContext(raw));
+        let ctx = unsafe { Context::from_raw(raw) };
+        ctx.devices()
+            .iter()
+            .all(|d| d.svm_capabilities().fine_grain_system())
+    }
+}
@@ -1,60 +1,163 @@
 #![cfg(cl)]
 #![deny(warnings)]
 
+#[cfg(cl_build_error)]
+compile_error!(env!("CLRT_BUILD_ERROR"));
+
 #[macro_use]
 #[allow(
     non_camel_case_types,
     non_snake_case,
     non_upper_case_globals,
-    clippy::approx_constant
+    clippy::approx_constant,
+    clippy::type_complexity,
+    clippy::too_many_arguments,
+    clippy::missing_safety_doc
 )]
 pub mod bindings {
     include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
     pub const NO_ERR: cl_int = CL_SUCCESS as _;
 
+    #[cfg(feature = "dynamic")]
+    #[inline]
+    pub fn opencl() -> &'static OpenCL {
+        match crate::library::library() {
+            Ok(lib) => lib,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// 取得 OpenCL™ 函数，运行时加载模式下从函数表中查找。
+    #[cfg(not(feature = "dynamic"))]
+    #[doc(hidden)]
     #[macro_export]
-    macro_rules! cl {
-        ($f:expr) => {{
+    macro_rules! cl_fn {
+        ($f:ident) => {
+            $crate::bindings::$f
+        };
+    }
+
+    /// 取得 OpenCL™ 函数，运行时加载模式下从函数表中查找。
+    #[cfg(feature = "dynamic")]
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! cl_fn {
+        ($f:ident) => {
+            match &$crate::bindings::opencl().$f {
+                Ok(f) => *f,
+                Err(_) => panic!("{} is not found in OpenCL library", stringify!($f)),
+            }
+        };
+    }
+
+    #[macro_export]
+    macro_rules! try_cl {
+        ($f:ident($($arg:expr),* $(,)?)) => {{
             #[allow(unused_imports)]
             use $crate::bindings::*;
             #[allow(unused_unsafe, clippy::macro_metavars_in_unsafe)]
-            let err = unsafe { $f };
-            assert_eq!(err, NO_ERR);
+            let err = unsafe { $crate::cl_fn!($f)($($arg),*) };
+            $crate::Error::check(stringify!($f), err)
         }};
 
-        ($err: ident => $f:expr) => {{
+        ($err: ident => $f:ident($($arg:expr),* $(,)?)) => {{
             #[allow(unused_imports)]
             use $crate::bindings::*;
 
             let mut $err = 0;
             #[allow(unused_unsafe, clippy::macro_metavars_in_unsafe)]
-            let ans = unsafe { $f };
-            assert_eq!($err, NO_ERR);
-
-            ans
+            let ans = unsafe { $crate::cl_fn!($f)($($arg),*) };
+            $crate::Error::check(stringify!($f), $err).map(|()| ans)
         }};
     }
+
+    #[macro_export]
+    macro_rules! cl {
+        ($($tt:tt)*) => {
+            match $crate::try_cl!($($tt)*) {
+                Ok(ans) => ans,
+                Err(e) => panic!("{e}"),
+            }
+        };
+    }
 }
 
+/// 定义与 OpenCL™ 枚举常量一一对应的 Rust 枚举。
+macro_rules! cl_enum {
+    ($(#[$attr:meta])* $vis:vis enum $name:ident: $raw:ty { $($(#[$vattr:meta])* $variant:ident = $value:ident,)+ }) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        #[repr(u32)]
+        $vis enum $name {
+            $($(#[$vattr])* $variant = $crate::bindings::$value,)+
+        }
+
+        impl $name {
+            #[inline]
+            #[allow(unused_doc_comments)]
+            pub fn from_raw(raw: $raw) -> Option<Self> {
+                match raw {
+                    $($(#[$vattr])* $crate::bindings::$value => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            pub fn as_raw(self) -> $raw {
+                self as _
+            }
+        }
+    };
+}
+
+mod buffer;
+mod cache;
 mod command_queue;
 mod context;
 mod device;
+mod error;
 mod event;
+mod image;
 mod kernel;
+#[cfg(feature = "dynamic")]
+mod library;
 mod node;
 mod platform;
 mod program;
+#[cfg(cl_2_0)]
 mod svm;
 
-pub use command_queue::CommandQueue;
-pub use context::Context;
-pub use device::Device;
-pub use event::{Event, UserEvent};
-pub use kernel::{Argument, Kernel};
+pub use buffer::{Buffer, BufferMap};
+pub use cache::ProgramCache;
+pub use command_queue::{
+    CommandQueue, QueueBuilder, QueuePriority, QueueProperties, QueueThrottle,
+};
+pub use context::{Context, ContextBuilder, ContextNotify};
+pub use device::{
+    AffinityDomain, Device, DeviceInfo, DeviceSelector, DeviceType, FpConfig, ImageLimits,
+    Partition, VectorWidths,
+};
+pub use error::Error;
+pub use event::{Event, EventFuture, EventStatus, ProfilingInfo, UserEvent};
+pub use image::{
+    AddressingMode, ChannelOrder, ChannelType, FilterMode, Image, ImageFormat, ImageMap,
+    ImageShape, Sampler,
+};
+pub use kernel::{
+    AccessQualifier, AddressQualifier, ArgError, ArgInfo, Argument, Kernel, Launch, LaunchError,
+};
+#[cfg(feature = "dynamic")]
+pub use library::{load_library, LoadError};
 pub use node::EventNode;
-pub use platform::Platform;
+pub use platform::{NameVersion, Platform, Profile, Version};
 pub use program::{BuildError, Program};
-pub use svm::{SvmBlob, SvmBlobMapped, SvmByte, SvmCapabilities, SvmMap};
+#[cfg(cl_2_1)]
+pub use svm::MigrateFlags;
+#[cfg(cl_2_0)]
+pub use svm::{
+    SvmBlob, SvmBlobMapped, SvmBox, SvmByte, SvmCapabilities, SvmElem, SvmFlags, SvmFreeCallback,
+    SvmMap, SvmMapPending, SvmMem, SvmPod, SvmVec,
+};
 
 use bindings::cl_uint;
 use std::{ffi::c_void, ptr::null_mut};
@@ -84,6 +187,19 @@ pub trait AsRaw {
         String::from_utf8(ans).unwrap()
     }
 
+    fn query_array<Ans: Copy>(&self, key: cl_uint) -> Vec<Ans> {
+        let mut size = 0;
+        self.query(key, 0, null_mut(), &mut size);
+        assert_eq!(size % size_of::<Ans>(), 0);
+
+        let mut ans = Vec::<Ans>::with_capacity(size / size_of::<Ans>());
+        self.query(key, size, ans.as_mut_ptr().cast(), &mut size);
+        assert_eq!(size, ans.capacity() * size_of::<Ans>());
+        unsafe { ans.set_len(ans.capacity()) };
+
+        ans
+    }
+
     fn query_value<Ans: Copy>(&self, key: cl_uint) -> Ans {
         let mut size = 0;
 
@@ -0,0 +1,88 @@
+//! 运行时加载 OpenCL™ 库。
+
+use crate::bindings::OpenCL;
+use std::{ffi::OsStr, fmt, sync::OnceLock};
+
+static LIBRARY: OnceLock<Result<OpenCL, LoadError>> = OnceLock::new();
+
+/// 未指定路径时依次尝试的库名。
+const DEFAULT_NAMES: &[&str] = if cfg!(windows) {
+    &["OpenCL.dll"]
+} else if cfg!(target_os = "macos") {
+    &["/System/Library/Frameworks/OpenCL.framework/OpenCL"]
+} else {
+    &["libOpenCL.so.1", "libOpenCL.so"]
+};
+
+/// 指定库路径的环境变量，优先于默认库名。
+const ENV_PATH: &str = "CLRT_OPENCL_LIBRARY";
+
+#[derive(Clone, Debug)]
+pub enum LoadError {
+    /// 库已经加载，或已经尝试过加载。
+    AlreadyLoaded,
+    /// 找不到库或库中缺少必要的符号。
+    NotFound(String),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::AlreadyLoaded => write!(f, "OpenCL library is already loaded"),
+            Self::NotFound(msg) => write!(f, "OpenCL library not found: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// 从 `path` 加载 OpenCL™ 库。
+///
+/// 必须在调用其他 OpenCL™ 功能之前调用，否则将自动加载默认的库并返回 [`LoadError::AlreadyLoaded`]。
+pub fn load_library(path: impl AsRef<OsStr>) -> Result<(), LoadError> {
+    let mut init = false;
+    let lib = LIBRARY.get_or_init(|| {
+        init = true;
+        open(path.as_ref())
+    });
+    if !init {
+        return Err(LoadError::AlreadyLoaded);
+    }
+    lib.as_ref().map(|_| ()).map_err(Clone::clone)
+}
+
+/// 取得已加载的库，未加载时按环境变量和默认库名加载。
+pub(crate) fn library() -> Result<&'static OpenCL, &'static LoadError> {
+    LIBRARY
+        .get_or_init(|| match std::env::var_os(ENV_PATH) {
+            Some(path) => open(&path),
+            None => {
+                let mut msgs = Vec::new();
+                for name in DEFAULT_NAMES {
+                    match open(name.as_ref()) {
+                        Ok(lib) => return Ok(lib),
+                        Err(LoadError::NotFound(msg)) => msgs.push(msg),
+                        Err(LoadError::AlreadyLoaded) => unreachable!(),
+                    }
+                }
+                Err(LoadError::NotFound(msgs.join("; ")))
+            }
+        })
+        .as_ref()
+}
+
+fn open(path: &OsStr) -> Result<OpenCL, LoadError> {
+    unsafe { OpenCL::new(path) }.map_err(|e| LoadError::NotFound(e.to_string()))
+}
+
+#[test]
+fn test() {
+    // 找不到库时没有平台
+    if library().is_err() {
+        assert!(crate::Platform::all().is_empty())
+    }
+    assert!(matches!(
+        load_library(DEFAULT_NAMES[0]),
+        Err(LoadError::AlreadyLoaded)
+    ));
+}
@@ -66,7 +66,7 @@ pub(crate) struct NodeParts<'a> {
     _phantom: PhantomData<&'a ()>,
 }
 
-pub(crate) fn destruct(event: Option<&mut EventNode>) -> NodeParts {
+pub(crate) fn destruct(event: Option<&mut EventNode>) -> NodeParts<'_> {
     match event {
         Some(EventNode { to_wait, to_record }) => NodeParts {
             num_events_in_wait_list: to_wait.len() as _,
@@ -1,11 +1,17 @@
 ﻿use crate::{
-    bindings::{clGetPlatformIDs, cl_platform_id, cl_uint},
+    bindings::{cl_platform_id, cl_uint},
     AsRaw,
 };
-use std::{ffi::c_void, fmt, ptr::null_mut};
+use std::{
+    cmp::Ordering,
+    ffi::c_void,
+    fmt,
+    hash::{Hash, Hasher},
+    ptr::null_mut,
+};
 
 #[repr(transparent)]
-pub struct Platform(cl_platform_id);
+pub struct Platform(pub(crate) cl_platform_id);
 
 impl AsRaw for Platform {
     type Raw = cl_platform_id;
@@ -15,6 +21,7 @@ impl AsRaw for Platform {
     }
 
     #[inline]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
     fn query(&self, key: cl_uint, val_size: usize, val: *mut c_void, size_ret: &mut usize) {
         cl!(clGetPlatformInfo(
             self.as_raw(),
@@ -27,12 +34,18 @@ impl AsRaw for Platform {
 }
 
 impl Platform {
+    /// 列出所有平台。运行时加载 OpenCL™ 库失败时返回空列表。
     pub fn all() -> Vec<Self> {
+        #[cfg(feature = "dynamic")]
+        if crate::library::library().is_err() {
+            return Vec::new();
+        }
+
         let mut num = 0;
-        unsafe { clGetPlatformIDs(0, null_mut(), &mut num) };
+        unsafe { cl_fn!(clGetPlatformIDs)(0, null_mut(), &mut num) };
 
         let mut ans = vec![null_mut(); num as _];
-        unsafe { clGetPlatformIDs(num, ans.as_mut_ptr(), &mut num) };
+        unsafe { cl_fn!(clGetPlatformIDs)(num, ans.as_mut_ptr(), &mut num) };
         assert_eq!(num as usize, ans.len());
 
         ans.into_iter().map(Self).collect()
@@ -47,29 +60,192 @@ impl Platform {
     #[inline]
     pub fn version(&self) -> Version {
         use crate::bindings::CL_PLATFORM_VERSION;
-        let ver = self.query_string(CL_PLATFORM_VERSION);
         // See <https://registry.khronos.org/OpenCL/specs/3.0-unified/html/OpenCL_API.html#CL_PLATFORM_VERSION>
-        let ver = ver
-            .strip_prefix("OpenCL ")
-            .expect("Version string should start with 'OpenCL '");
-        let (num, specific) = ver.split_once(' ').unwrap_or((ver, ""));
-
-        let (major, minor) = num.split_once('.').unwrap();
-        Version {
-            major: major.parse().unwrap(),
-            minor: minor.parse().unwrap(),
-            specific: specific.to_string(),
+        Version::parse_lossy(&self.query_string(CL_PLATFORM_VERSION))
+    }
+
+    #[inline]
+    pub fn vendor(&self) -> String {
+        use crate::bindings::CL_PLATFORM_VENDOR;
+        self.query_string(CL_PLATFORM_VENDOR)
+    }
+
+    #[inline]
+    pub fn profile(&self) -> Profile {
+        use crate::bindings::CL_PLATFORM_PROFILE;
+        match &*self.query_string(CL_PLATFORM_PROFILE) {
+            "EMBEDDED_PROFILE" => Profile::Embedded,
+            _ => Profile::Full,
+        }
+    }
+
+    #[inline]
+    pub fn extensions(&self) -> Vec<String> {
+        use crate::bindings::CL_PLATFORM_EXTENSIONS;
+        self.query_string(CL_PLATFORM_EXTENSIONS)
+            .split_whitespace()
+            .map(String::from)
+            .collect()
+    }
+
+    #[inline]
+    pub fn has_extension(&self, name: &str) -> bool {
+        use crate::bindings::CL_PLATFORM_EXTENSIONS;
+        self.query_string(CL_PLATFORM_EXTENSIONS)
+            .split_whitespace()
+            .any(|ext| ext == name)
+    }
+
+    /// 带版本的扩展列表。OpenCL™ 3.0 以下的平台返回 [`Error::Unsupported`](crate::Error::Unsupported)。
+    #[cfg(cl_3_0)]
+    pub fn extensions_with_version(&self) -> Result<Vec<NameVersion>, crate::Error> {
+        use crate::bindings::{
+            cl_name_version, CL_PLATFORM_EXTENSIONS_WITH_VERSION, CL_VERSION_MINOR_BITS,
+            CL_VERSION_MINOR_MASK, CL_VERSION_PATCH_BITS, CL_VERSION_PATCH_MASK,
+        };
+        if self.version() < Version::new(3, 0) {
+            return Err(crate::Error::Unsupported(
+                "CL_PLATFORM_EXTENSIONS_WITH_VERSION",
+            ));
+        }
+        Ok(self
+            .query_array::<cl_name_version>(CL_PLATFORM_EXTENSIONS_WITH_VERSION)
+            .into_iter()
+            .map(|ext| {
+                let name = ext.name.map(|c| c as u8);
+                let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+                NameVersion {
+                    name: String::from_utf8_lossy(&name[..len]).into_owned(),
+                    major: ext.version >> (CL_VERSION_MINOR_BITS + CL_VERSION_PATCH_BITS),
+                    minor: (ext.version >> CL_VERSION_PATCH_BITS) & CL_VERSION_MINOR_MASK,
+                    patch: ext.version & CL_VERSION_PATCH_MASK,
+                }
+            })
+            .collect())
+    }
+
+    /// 主机计时器的分辨率（纳秒）。OpenCL™ 2.1 以下或不支持 `clGetHostTimer` 的平台为 0。
+    #[cfg(cl_2_1)]
+    #[inline]
+    pub fn host_timer_resolution(&self) -> u64 {
+        use crate::bindings::{cl_ulong, CL_PLATFORM_HOST_TIMER_RESOLUTION};
+        if self.version() < Version::new(2, 1) {
+            return 0;
         }
+        self.query_value::<cl_ulong>(CL_PLATFORM_HOST_TIMER_RESOLUTION)
+    }
+
+    /// ICD 加载器用于区分平台的函数名后缀。平台不支持 `cl_khr_icd` 时返回 `None`。
+    #[inline]
+    pub fn icd_suffix(&self) -> Option<String> {
+        use crate::bindings::CL_PLATFORM_ICD_SUFFIX_KHR;
+        self.has_extension("cl_khr_icd")
+            .then(|| self.query_string(CL_PLATFORM_ICD_SUFFIX_KHR))
+    }
+}
+
+/// 平台或设备支持的规范子集。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Profile {
+    Full,
+    Embedded,
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Full => write!(f, "FULL_PROFILE"),
+            Self::Embedded => write!(f, "EMBEDDED_PROFILE"),
+        }
+    }
+}
+
+/// 带版本的扩展名。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NameVersion {
+    pub name: String,
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl fmt::Display for NameVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}.{}.{}",
+            self.name, self.major, self.minor, self.patch
+        )
     }
 }
 
+/// OpenCL™ 版本。比较、判等和哈希只考虑主次版本号，不考虑厂商信息。
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Version {
     major: u32,
     minor: u32,
     specific: String,
 }
 
+impl Version {
+    #[inline]
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self {
+            major,
+            minor,
+            specific: String::new(),
+        }
+    }
+
+    #[inline]
+    pub const fn major(&self) -> u32 {
+        self.major
+    }
+
+    #[inline]
+    pub const fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// 厂商附加的信息。
+    #[inline]
+    pub fn specific(&self) -> &str {
+        &self.specific
+    }
+
+    /// 解析 `OpenCL<space><major.minor><space><vendor-specific information>` 格式的版本字符串。
+    ///
+    /// 也接受 `OpenCL C 1.2`、`OpenCL 3.0.1 ...` 和省略 `OpenCL` 的格式，找不到版本号时返回 `None`。
+    pub fn parse(ver: &str) -> Option<Self> {
+        fn number(s: &str) -> Option<(u32, &str)> {
+            let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            Some((s[..end].parse().ok()?, &s[end..]))
+        }
+
+        let ver = ver.trim();
+        let ver = ver.strip_prefix("OpenCL").unwrap_or(ver).trim_start();
+        let ver = ver.strip_prefix("C ").unwrap_or(ver).trim_start();
+        let (num, specific) = ver.split_once(char::is_whitespace).unwrap_or((ver, ""));
+
+        let (major, rest) = number(num)?;
+        let (minor, _) = number(rest.strip_prefix('.')?)?;
+        Some(Self {
+            major,
+            minor,
+            specific: specific.trim().to_string(),
+        })
+    }
+
+    /// 解析查询到的版本字符串，无法解析时视为 0.0 并保留原字符串。
+    pub(crate) fn parse_lossy(ver: &str) -> Self {
+        Self::parse(ver).unwrap_or_else(|| Self {
+            specific: ver.to_string(),
+            ..Self::new(0, 0)
+        })
+    }
+}
+
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "OpenCL {}.{}", self.major, self.minor)?;
@@ -86,19 +262,70 @@ impl PartialEq for Version {
     }
 }
 
+impl Eq for Version {}
+
 impl PartialOrd for Version {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        use std::cmp::Ordering::Equal;
-        Some(match self.major.cmp(&other.major) {
-            Equal => self.minor.cmp(&other.minor),
-            ord => ord,
-        })
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor).cmp(&(other.major, other.minor))
+    }
+}
+
+impl Hash for Version {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.major, self.minor).hash(state)
     }
 }
 
 #[test]
 fn test() {
+    let parse = |s| Version::parse(s).map(|v| (v.major(), v.minor(), v.specific().to_string()));
+    assert_eq!(
+        parse("OpenCL 3.0 CUDA 12.4.131"),
+        Some((3, 0, "CUDA 12.4.131".into()))
+    );
+    assert_eq!(
+        parse("OpenCL 2.1 AMD-APP (3590.0)"),
+        Some((2, 1, "AMD-APP (3590.0)".into()))
+    );
+    assert_eq!(parse("OpenCL 1.2"), Some((1, 2, "".into())));
+    assert_eq!(parse("OpenCL C 1.2 "), Some((1, 2, "".into())));
+    assert_eq!(parse("OpenCL 3.0.1 "), Some((3, 0, "".into())));
+    assert_eq!(parse("2.0"), Some((2, 0, "".into())));
+    assert_eq!(parse("OpenCL unknown"), None);
+    assert_eq!(Version::parse_lossy("garbage"), Version::new(0, 0));
+    assert!(Version::new(1, 2) < Version::new(2, 0));
+    assert_eq!(
+        Version::parse("OpenCL 2.0 foo").unwrap(),
+        Version::parse("OpenCL 2.0 bar").unwrap()
+    );
+
     for platform in Platform::all() {
-        println!("{} ({})", platform.name(), platform.version())
+        println!(
+            "{} ({}) {} {}",
+            platform.name(),
+            platform.version(),
+            platform.vendor(),
+            platform.profile()
+        );
+        println!("  icd suffix: {:?}", platform.icd_suffix());
+        #[cfg(cl_2_1)]
+        println!("  host timer: {} ns", platform.host_timer_resolution());
+        #[cfg(cl_3_0)]
+        match platform.extensions_with_version() {
+            Ok(exts) => assert_eq!(exts.len(), platform.extensions().len()),
+            Err(e) => assert!(matches!(e, crate::Error::Unsupported(_))),
+        }
+        for ext in platform.extensions() {
+            println!("  - {ext}")
+        }
     }
 }
@@ -1,12 +1,15 @@
 ﻿use crate::{
     bindings::{
-        clBuildProgram, clCreateKernel, cl_int, cl_program, CL_BUILD_PROGRAM_FAILURE,
-        CL_INVALID_KERNEL_NAME, NO_ERR,
+        cl_device_id, cl_program, cl_uint, CL_BUILD_PROGRAM_FAILURE, CL_COMPILE_PROGRAM_FAILURE,
+        CL_INVALID_KERNEL_NAME, CL_LINK_PROGRAM_FAILURE, NO_ERR,
     },
     kernel::Kernel,
-    AsRaw, Context,
+    AsRaw, Context, Device, Error,
+};
+use std::{
+    ffi::{c_void, CStr, CString},
+    ptr::{null, null_mut},
 };
-use std::{ffi::CStr, ptr::null_mut};
 
 #[repr(transparent)]
 pub struct Program(cl_program);
@@ -14,7 +17,16 @@ pub struct Program(cl_program);
 #[derive(Clone, Debug)]
 pub enum BuildError {
     BuildFailed(String),
-    Others(cl_int),
+    /// 驱动拒绝的二进制，每项是设备在上下文中的序号和对应的状态。
+    InvalidBinary(Vec<(usize, Error)>),
+    Others(Error),
+}
+
+impl From<Error> for BuildError {
+    #[inline]
+    fn from(value: Error) -> Self {
+        Self::Others(value)
+    }
 }
 
 impl Context {
@@ -23,53 +35,249 @@ impl Context {
         source: &str,
         options: impl AsRef<CStr>,
     ) -> Result<Program, BuildError> {
-        let mut str = source.as_ptr().cast();
-        let len = source.len();
-        let program =
-            cl!(err => clCreateProgramWithSource(self.as_raw(), 1, &mut str, &len, &mut err));
+        let program = self.create_from_source(source)?;
+        self.build(program, options.as_ref())
+    }
 
-        let [device] = self.devices() else {
-            panic!("multi-device context is not supported")
-        };
-        let device = unsafe { device.as_raw() };
+    /// 从 [`Program::binaries`] 取得的二进制构建程序，每个设备一份二进制。
+    pub fn build_from_binary(
+        &self,
+        binaries: &[impl AsRef<[u8]>],
+        options: impl AsRef<CStr>,
+    ) -> Result<Program, BuildError> {
+        let devices = self
+            .devices()
+            .iter()
+            .map(|d| unsafe { d.as_raw() })
+            .collect::<Vec<_>>();
+        if binaries.len() != devices.len() {
+            return Err(Error::InvalidValue("clCreateProgramWithBinary").into());
+        }
+
+        let lengths = binaries
+            .iter()
+            .map(|b| b.as_ref().len())
+            .collect::<Vec<_>>();
+        let ptrs = binaries
+            .iter()
+            .map(|b| b.as_ref().as_ptr())
+            .collect::<Vec<_>>();
+        let mut status = vec![0; devices.len()];
+        let program = try_cl!(err => clCreateProgramWithBinary(
+            self.as_raw(),
+            devices.len() as _,
+            devices.as_ptr(),
+            lengths.as_ptr(),
+            ptrs.as_ptr().cast_mut(),
+            status.as_mut_ptr(),
+            &mut err
+        ));
+        let rejected = status
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, code)| {
+                Error::check("clCreateProgramWithBinary", code)
+                    .err()
+                    .map(|e| (i, e))
+            })
+            .collect::<Vec<_>>();
+        match program {
+            Ok(program) if rejected.is_empty() => self.build(program, options.as_ref()),
+            Ok(program) => {
+                cl!(clReleaseProgram(program));
+                Err(BuildError::InvalidBinary(rejected))
+            }
+            Err(_) if !rejected.is_empty() => Err(BuildError::InvalidBinary(rejected)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 从 SPIR-V 等中间语言构建程序，需要 OpenCL™ 2.1。
+    #[cfg(cl_2_1)]
+    pub fn build_from_il(
+        &self,
+        il: &[u8],
+        options: impl AsRef<CStr>,
+    ) -> Result<Program, BuildError> {
+        for device in self.devices() {
+            device.require(2, 1, "clCreateProgramWithIL")?;
+        }
+        let program = try_cl!(err => clCreateProgramWithIL(
+            self.as_raw(),
+            il.as_ptr().cast(),
+            il.len(),
+            &mut err
+        ))?;
+        self.build(program, options.as_ref())
+    }
+
+    /// 将源码编译为目标程序，`headers` 是源码中 `#include` 的名字和对应的头文件程序。
+    pub fn compile(
+        &self,
+        source: &str,
+        options: impl AsRef<CStr>,
+        headers: &[(&CStr, &Program)],
+    ) -> Result<Program, BuildError> {
+        let program = self.create_from_source(source)?;
+        let options = with_arg_info(options.as_ref());
+
+        let names = headers.iter().map(|(n, _)| n.as_ptr()).collect::<Vec<_>>();
+        let programs = headers.iter().map(|(_, p)| p.0).collect::<Vec<_>>();
         match unsafe {
-            clBuildProgram(
+            cl_fn!(clCompileProgram)(
                 program,
-                1,
-                &device,
+                0,
+                null(),
+                options.as_ptr(),
+                headers.len() as _,
+                if headers.is_empty() {
+                    null()
+                } else {
+                    programs.as_ptr()
+                },
+                if headers.is_empty() {
+                    null_mut()
+                } else {
+                    names.as_ptr().cast_mut()
+                },
+                None,
+                null_mut(),
+            )
+        } {
+            NO_ERR => Ok(Program(program)),
+            CL_COMPILE_PROGRAM_FAILURE => {
+                let log = self.build_logs(program);
+                cl!(clReleaseProgram(program));
+                Err(BuildError::BuildFailed(log))
+            }
+            err => {
+                cl!(clReleaseProgram(program));
+                Err(Error::check("clCompileProgram", err).unwrap_err().into())
+            }
+        }
+    }
+
+    /// 链接已编译的程序。在 `options` 中传入 `-create-library` 以生成库。
+    pub fn link(
+        &self,
+        programs: &[&Program],
+        options: impl AsRef<CStr>,
+    ) -> Result<Program, BuildError> {
+        let programs = programs.iter().map(|p| p.0).collect::<Vec<_>>();
+
+        let mut err = 0;
+        let program = unsafe {
+            cl_fn!(clLinkProgram)(
+                self.as_raw(),
+                0,
+                null(),
                 options.as_ref().as_ptr(),
+                programs.len() as _,
+                programs.as_ptr(),
                 None,
                 null_mut(),
+                &mut err,
             )
+        };
+        match err {
+            NO_ERR => Ok(Program(program)),
+            CL_LINK_PROGRAM_FAILURE if !program.is_null() => {
+                let log = self.build_logs(program);
+                cl!(clReleaseProgram(program));
+                Err(BuildError::BuildFailed(log))
+            }
+            err => {
+                if !program.is_null() {
+                    cl!(clReleaseProgram(program))
+                }
+                Err(Error::check("clLinkProgram", err).unwrap_err().into())
+            }
+        }
+    }
+
+    fn create_from_source(&self, source: &str) -> Result<cl_program, Error> {
+        let mut str = source.as_ptr().cast();
+        let len = source.len();
+        try_cl!(err => clCreateProgramWithSource(self.as_raw(), 1, &mut str, &len, &mut err))
+    }
+
+    /// 为上下文的所有设备构建程序。
+    fn build(&self, program: cl_program, options: &CStr) -> Result<Program, BuildError> {
+        let options = with_arg_info(options);
+        match unsafe {
+            cl_fn!(clBuildProgram)(program, 0, null(), options.as_ptr(), None, null_mut())
         } {
             NO_ERR => Ok(Program(program)),
             CL_BUILD_PROGRAM_FAILURE => {
-                let mut size = 0;
-                cl!(clGetProgramBuildInfo(
-                    program,
-                    device,
-                    CL_PROGRAM_BUILD_LOG,
-                    0,
-                    null_mut(),
-                    &mut size
-                ));
-                let mut log = vec![0u8; size];
-                cl!(clGetProgramBuildInfo(
-                    program,
-                    device,
-                    CL_PROGRAM_BUILD_LOG,
-                    size,
-                    log.as_mut_ptr().cast(),
-                    &mut size
-                ));
-                assert_eq!(size, log.len());
+                let log = self.build_logs(program);
+                cl!(clReleaseProgram(program));
+                Err(BuildError::BuildFailed(log))
+            }
+            err => {
                 cl!(clReleaseProgram(program));
+                Err(Error::check("clBuildProgram", err).unwrap_err().into())
+            }
+        }
+    }
+}
 
-                Err(BuildError::BuildFailed(String::from_utf8(log).unwrap()))
+impl Context {
+    /// 收集所有设备的构建日志。多个设备时每段日志以设备名开头，省略空日志。
+    fn build_logs(&self, program: cl_program) -> String {
+        if let [device] = self.devices() {
+            return build_log(program, unsafe { device.as_raw() });
+        }
+        let mut ans = String::new();
+        for device in self.devices() {
+            let log = build_log(program, unsafe { device.as_raw() });
+            if !log.trim().is_empty() {
+                ans.push_str(&format!("{}:\n{log}\n", device.name()))
             }
-            err => Err(BuildError::Others(err)),
         }
+        ans
+    }
+}
+
+/// 总是保留核函数参数信息，以支持 [`Kernel::arg_info`]。
+fn with_arg_info(options: &CStr) -> CString {
+    const ARG_INFO: &[u8] = b"-cl-kernel-arg-info";
+
+    let options = options.to_bytes();
+    if options
+        .split(u8::is_ascii_whitespace)
+        .any(|opt| opt == ARG_INFO)
+    {
+        return CString::new(options).unwrap();
     }
+    let mut ans = options.to_vec();
+    if !ans.is_empty() {
+        ans.push(b' ')
+    }
+    ans.extend_from_slice(ARG_INFO);
+    CString::new(ans).unwrap()
+}
+
+fn build_log(program: cl_program, device: cl_device_id) -> String {
+    let mut size = 0;
+    cl!(clGetProgramBuildInfo(
+        program,
+        device,
+        CL_PROGRAM_BUILD_LOG,
+        0,
+        null_mut(),
+        &mut size
+    ));
+    let mut log = vec![0u8; size];
+    cl!(clGetProgramBuildInfo(
+        program,
+        device,
+        CL_PROGRAM_BUILD_LOG,
+        size,
+        log.as_mut_ptr().cast(),
+        &mut size
+    ));
+    assert_eq!(size, log.len());
+    String::from_utf8(log).unwrap()
 }
 
 unsafe impl Send for Program {}
@@ -94,9 +302,49 @@ impl AsRaw for Program {
     unsafe fn as_raw(&self) -> Self::Raw {
         self.0
     }
+
+    #[inline]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn query(&self, key: cl_uint, val_size: usize, val: *mut c_void, size_ret: &mut usize) {
+        cl!(clGetProgramInfo(
+            self.as_raw(),
+            key,
+            val_size,
+            val,
+            size_ret
+        ))
+    }
 }
 
 impl Program {
+    /// 读取程序在每个设备上的二进制，可用于 [`Context::build_from_binary`]。
+    pub fn binaries(&self) -> Vec<Vec<u8>> {
+        use crate::bindings::{CL_PROGRAM_BINARIES, CL_PROGRAM_BINARY_SIZES};
+
+        let sizes = self.query_array::<usize>(CL_PROGRAM_BINARY_SIZES);
+        let mut binaries = sizes.into_iter().map(|n| vec![0u8; n]).collect::<Vec<_>>();
+        let mut ptrs = binaries
+            .iter_mut()
+            .map(|b| b.as_mut_ptr())
+            .collect::<Vec<_>>();
+
+        let mut size = 0;
+        self.query(
+            CL_PROGRAM_BINARIES,
+            size_of_val(ptrs.as_slice()),
+            ptrs.as_mut_ptr().cast(),
+            &mut size,
+        );
+        assert_eq!(size, size_of_val(ptrs.as_slice()));
+        binaries
+    }
+
+    /// 程序在 `device` 上的构建日志，包括构建成功时的警告。
+    #[inline]
+    pub fn build_log(&self, device: &Device) -> String {
+        build_log(self.0, unsafe { device.as_raw() })
+    }
+
     pub fn kernels(&self) -> Vec<Kernel> {
         let mut num = 0;
         cl!(clCreateKernelsInProgram(self.0, 0, null_mut(), &mut num));
@@ -115,11 +363,11 @@ impl Program {
 
     pub fn get_kernel(&self, name: impl AsRef<CStr>) -> Option<Kernel> {
         let mut err = 0;
-        let kernel = unsafe { clCreateKernel(self.0, name.as_ref().as_ptr(), &mut err) };
+        let kernel = unsafe { cl_fn!(clCreateKernel)(self.0, name.as_ref().as_ptr(), &mut err) };
         match err {
             NO_ERR => Some(Kernel(kernel)),
             CL_INVALID_KERNEL_NAME => None,
-            _ => panic!("clCreateKernel failed with error code {err}"),
+            _ => panic!("{}", Error::check("clCreateKernel", err).unwrap_err()),
         }
     }
 }
@@ -159,3 +407,88 @@ kernel void saxpy_float (global float* z,
         }
     }
 }
+
+#[test]
+fn test_binary() {
+    use std::ffi::CString;
+
+    const PROGRAM_SOURCE: &str =
+        "kernel void add(global int* x, int a) { x[get_global_id(0)] += a; }";
+
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let context = device.context();
+            let program = context
+                .build_from_source(PROGRAM_SOURCE, CString::default())
+                .unwrap();
+            let binaries = program.binaries();
+            assert_eq!(binaries.len(), 1);
+
+            let program = context
+                .build_from_binary(&binaries, CString::default())
+                .unwrap();
+            assert!(program.get_kernel(c"add").is_some());
+
+            match context.build_from_binary(&[[0u8; 16]], CString::default()) {
+                Err(BuildError::InvalidBinary(rejected)) => assert_eq!(rejected[0].0, 0),
+                Err(BuildError::Others(Error::InvalidBinary(_)))
+                | Err(BuildError::BuildFailed(_)) => {}
+                _ => panic!("Invalid binary should be caught"),
+            }
+            assert!(matches!(
+                context.build_from_binary(&[[0u8; 16], [0u8; 16]], CString::default()),
+                Err(BuildError::Others(Error::InvalidValue(_)))
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_compile_link() {
+    const HEADER: &str = "int twice(int x);";
+    const LIBRARY: &str = r#"#include "twice.h"
+int twice(int x) { return 2 * x; }"#;
+    const SOURCE: &str = r#"#include "twice.h"
+kernel void apply(global int* x) { x[get_global_id(0)] = twice(x[get_global_id(0)]); }"#;
+
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let context = device.context();
+            let header = context.create_from_source(HEADER).map(Program).unwrap();
+            let headers = [(c"twice.h", &header)];
+
+            let library = context.compile(LIBRARY, c"", &headers).unwrap();
+            let library = context.link(&[&library], c"-create-library").unwrap();
+            let object = context.compile(SOURCE, c"", &headers).unwrap();
+            let program = context.link(&[&object, &library], c"").unwrap();
+            assert!(program.get_kernel(c"apply").is_some());
+
+            match context.compile("#error Error in source code", c"", &[]) {
+                Err(BuildError::BuildFailed(log)) => println!("Compile log: {log}"),
+                _ => panic!("Error in source code should be caught"),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_multi_device() {
+    const PROGRAM_SOURCE: &str = "kernel void fill(global int* x) { x[get_global_id(0)] = 1; }";
+
+    for platform in crate::Platform::all() {
+        let devices = platform.devices();
+        if devices.is_empty() {
+            continue;
+        }
+        let context = Context::new(&devices);
+        let program = context.build_from_source(PROGRAM_SOURCE, c"").unwrap();
+        assert_eq!(program.binaries().len(), devices.len());
+        for device in &devices {
+            println!("{}: {}", device.name(), program.build_log(device))
+        }
+        assert!(matches!(
+            context.build_from_source("#error", c""),
+            Err(BuildError::BuildFailed(_))
+        ));
+    }
+}
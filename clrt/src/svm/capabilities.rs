@@ -6,6 +6,7 @@ use crate::bindings::{
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct SvmCapabilities(cl_device_svm_capabilities);
 
@@ -1,65 +1,240 @@
-﻿use super::SvmByte;
+﻿use super::{SvmBox, SvmElem, SvmPod, SvmVec};
 use crate::{
-    bindings::{CL_MAP_READ, CL_MAP_WRITE, CL_MAP_WRITE_INVALIDATE_REGION},
+    bindings::{cl_event, cl_uint, CL_MAP_READ, CL_MAP_WRITE, CL_MAP_WRITE_INVALIDATE_REGION},
     node::{destruct, EventNode, NodeParts},
-    AsRaw, CommandQueue,
+    AsRaw, CommandQueue, Error, Event, EventStatus,
 };
 use std::{
     ffi::c_void,
     mem::forget,
     ops::{Deref, DerefMut},
-    ptr::{null, null_mut},
     slice::from_raw_parts_mut,
 };
 
-#[repr(transparent)]
-pub struct SvmMap<'a, const W: bool>(&'a mut [u8]);
+/// 映射到主机的 SVM 区域，`W` 表示是否可写。
+///
+/// 映射期间独占借用 SVM 内存，因此不能释放或传给核函数。释放时自动解除映射。
+pub struct SvmMap<'a, T, const W: bool> {
+    queue: &'a CommandQueue,
+    mem: &'a mut [T],
+    fine_grain: bool,
+}
+
+/// 可以映射到主机的 SVM 内存。
+pub trait SvmMem<T> {
+    fn as_svm_mut(&mut self) -> &mut [SvmElem<T>];
+
+    /// 内存是否以细粒度分配，细粒度内存映射时只记录事件。无法判断时应返回 `false`。
+    #[inline]
+    fn is_fine_grain(&self) -> bool {
+        false
+    }
+}
+
+impl<T> SvmMem<T> for [SvmElem<T>] {
+    #[inline]
+    fn as_svm_mut(&mut self) -> &mut [SvmElem<T>] {
+        self
+    }
+}
+
+impl<T> SvmMem<T> for SvmVec<T> {
+    #[inline]
+    fn as_svm_mut(&mut self) -> &mut [SvmElem<T>] {
+        self
+    }
+
+    #[inline]
+    fn is_fine_grain(&self) -> bool {
+        self.flags().is_fine_grain()
+    }
+}
+
+impl<T> SvmMem<T> for SvmBox<T> {
+    #[inline]
+    fn as_svm_mut(&mut self) -> &mut [SvmElem<T>] {
+        self.as_mut_slice()
+    }
+
+    #[inline]
+    fn is_fine_grain(&self) -> bool {
+        self.flags().is_fine_grain()
+    }
+}
 
-impl<const W_: bool> Drop for SvmMap<'_, W_> {
+impl<T, const W_: bool> Drop for SvmMap<'_, T, W_> {
     fn drop(&mut self) {
-        panic!("SvmMap should not be dropped manually")
+        let _ = self.queue.unmap_(
+            self.mem.as_mut_ptr().cast(),
+            size_of_val(self.mem),
+            self.fine_grain,
+            None,
+        );
+    }
+}
+
+impl<T, const W_: bool> SvmMap<'_, T, W_> {
+    /// 解除映射，与直接释放映射相同。
+    #[inline]
+    pub fn unmap(self) {
+        self.unmap_with(None)
+    }
+
+    /// 解除映射，等待 `event` 中的事件，并记录解除映射的事件。
+    pub fn unmap_with(self, event: Option<&mut EventNode>) {
+        let ptr = self.mem.as_mut_ptr().cast();
+        let len = size_of_val(self.mem);
+        self.queue.unmap_(ptr, len, self.fine_grain, event).unwrap();
+        forget(self)
+    }
+
+    /// 在 `wait` 中的事件完成后解除映射，不阻塞，返回解除映射的事件。
+    pub fn unmap_async(self, wait: impl IntoIterator<Item = Event>) -> Event {
+        let mut node = EventNode::new(wait, true);
+        self.unmap_with(Some(&mut node));
+        node.take().unwrap()
+    }
+}
+
+/// 尚未完成的映射，由 `*_async` 映射方法创建。
+///
+/// 映射事件完成后才能取出守卫访问数据。未取出守卫就释放时，在映射完成后解除映射。
+pub struct SvmMapPending<'a, G> {
+    queue: &'a CommandQueue,
+    guard: Option<G>,
+    event: Event,
+}
+
+impl<G> Drop for SvmMapPending<'_, G> {
+    fn drop(&mut self) {
+        if let Some(guard) = self.guard.take() {
+            // 乱序队列中解除映射需要显式等待映射完成
+            self.queue.wait(&self.event);
+            drop(guard)
+        }
+    }
+}
+
+impl<'a, G> SvmMapPending<'a, G> {
+    #[inline]
+    pub(super) fn new(queue: &'a CommandQueue, guard: G, event: Event) -> Self {
+        Self {
+            queue,
+            guard: Some(guard),
+            event,
+        }
+    }
+
+    #[inline]
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// 判断映射是否已经完成，不会阻塞。
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.event.status() == EventStatus::Complete
+    }
+
+    /// 阻塞直到映射完成，然后取出守卫。
+    pub fn wait(mut self) -> G {
+        self.event.wait();
+        self.guard.take().unwrap()
+    }
+
+    /// 异步等待映射完成，然后取出守卫。
+    pub async fn ready(mut self) -> Result<G, Error> {
+        self.event.clone().await?;
+        Ok(self.guard.take().unwrap())
     }
 }
 
 impl CommandQueue {
-    pub fn map<'a>(&self, mem: &'a [SvmByte]) -> SvmMap<'a, false> {
-        let flags = CL_MAP_READ;
+    #[inline]
+    pub fn map<'a, T: SvmPod>(
+        &'a self,
+        mem: &'a mut (impl SvmMem<T> + ?Sized),
+    ) -> SvmMap<'a, T, false> {
+        self.map_async(mem, []).0.wait()
+    }
 
-        let ptr = mem.as_ptr().cast_mut();
-        let len = mem.len();
-        self.map_(ptr.cast(), len, flags, None);
-        self.finish();
-        SvmMap(unsafe { from_raw_parts_mut(ptr.cast(), len) })
+    #[inline]
+    pub fn map_mut<'a, T: SvmPod>(
+        &'a self,
+        mem: &'a mut (impl SvmMem<T> + ?Sized),
+        readable: bool,
+    ) -> SvmMap<'a, T, true> {
+        self.map_mut_async(mem, readable, []).0.wait()
     }
 
-    pub fn map_mut<'a>(&self, mem: &'a mut [SvmByte], readable: bool) -> SvmMap<'a, true> {
+    /// 在 `wait` 中的事件完成后映射 `mem` 以供读取，不阻塞队列，返回尚未完成的映射和映射事件。
+    #[inline]
+    pub fn map_async<'a, T: SvmPod>(
+        &'a self,
+        mem: &'a mut (impl SvmMem<T> + ?Sized),
+        wait: impl IntoIterator<Item = Event>,
+    ) -> (SvmMapPending<'a, SvmMap<'a, T, false>>, Event) {
+        self.map_typed(mem, CL_MAP_READ, wait)
+    }
+
+    /// 在 `wait` 中的事件完成后映射 `mem` 以供写入，不阻塞队列，返回尚未完成的映射和映射事件。
+    pub fn map_mut_async<'a, T: SvmPod>(
+        &'a self,
+        mem: &'a mut (impl SvmMem<T> + ?Sized),
+        readable: bool,
+        wait: impl IntoIterator<Item = Event>,
+    ) -> (SvmMapPending<'a, SvmMap<'a, T, true>>, Event) {
         let flags = if readable {
             CL_MAP_READ | CL_MAP_WRITE
         } else {
             CL_MAP_WRITE_INVALIDATE_REGION
         };
+        self.map_typed(mem, flags, wait)
+    }
 
+    fn map_typed<'a, T: SvmPod, const W: bool>(
+        &'a self,
+        mem: &'a mut (impl SvmMem<T> + ?Sized),
+        flags: u32,
+        wait: impl IntoIterator<Item = Event>,
+    ) -> (SvmMapPending<'a, SvmMap<'a, T, W>>, Event) {
+        let fine_grain = mem.is_fine_grain();
+        let mem = mem.as_svm_mut();
         let ptr = mem.as_mut_ptr();
         let len = mem.len();
-        self.map_(ptr.cast(), len, flags, None);
-        self.finish();
-        SvmMap(unsafe { from_raw_parts_mut(ptr.cast(), len) })
+        let mut node = EventNode::new(wait, true);
+        self.map_(
+            ptr.cast(),
+            size_of_val(mem),
+            flags,
+            fine_grain,
+            Some(&mut node),
+        );
+        let event = node.take().unwrap();
+        let map = SvmMap {
+            queue: self,
+            mem: unsafe { from_raw_parts_mut(ptr.cast(), len) },
+            fine_grain,
+        };
+        (SvmMapPending::new(self, map, event.clone()), event)
     }
 
+    /// `fine_grain` 为真或设备支持细粒度系统 SVM 时内存不需要映射，只用屏障记录事件。
     pub(super) fn map_(
         &self,
         ptr: *mut c_void,
         len: usize,
         flags: u32,
+        fine_grain: bool,
         event: Option<&mut EventNode>,
     ) {
-        if !self.fine_grain_svm() && len > 0 {
-            let NodeParts {
-                num_events_in_wait_list,
-                event_wait_list,
-                event,
-                ..
-            } = destruct(event);
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        if !fine_grain && !self.fine_grain_system_svm() && len > 0 {
             cl!(clEnqueueSVMMap(
                 self.as_raw(),
                 CL_FALSE,
@@ -70,37 +245,63 @@ impl CommandQueue {
                 event_wait_list,
                 event,
             ))
-        } else if let Some(node) = event {
-            self.wait_raw(node.to_wait())
+        } else {
+            self.barrier_(num_events_in_wait_list, event_wait_list, event)
+        }
+    }
+
+    pub(super) fn unmap_(
+        &self,
+        ptr: *mut c_void,
+        len: usize,
+        fine_grain: bool,
+        event: Option<&mut EventNode>,
+    ) -> Result<(), Error> {
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        if !fine_grain && !self.fine_grain_system_svm() && len > 0 {
+            try_cl!(clEnqueueSVMUnmap(
+                self.as_raw(),
+                ptr,
+                num_events_in_wait_list,
+                event_wait_list,
+                event,
+            ))
+        } else {
+            self.barrier_(num_events_in_wait_list, event_wait_list, event);
+            Ok(())
         }
     }
 
-    pub fn unmap<const W_: bool>(&self, mem: SvmMap<'_, W_>) {
-        if !self.fine_grain_svm() && !mem.0.is_empty() {
-            cl!(clEnqueueSVMUnmap(
+    /// 不需要实际映射时，用屏障代替映射命令，保留依赖关系并记录事件。
+    pub(super) fn barrier_(&self, num: cl_uint, list: *const cl_event, event: *mut cl_event) {
+        if num > 0 || !event.is_null() {
+            cl!(clEnqueueBarrierWithWaitList(
                 self.as_raw(),
-                mem.0.as_mut_ptr().cast(),
-                0,
-                null(),
-                null_mut()
+                num,
+                list,
+                event
             ))
         }
-        forget(mem)
     }
 }
 
-impl<const W_: bool> Deref for SvmMap<'_, W_> {
-    type Target = [u8];
+impl<T, const W_: bool> Deref for SvmMap<'_, T, W_> {
+    type Target = [T];
     #[inline]
     fn deref(&self) -> &Self::Target {
-        self.0
+        self.mem
     }
 }
 
-impl DerefMut for SvmMap<'_, true> {
+impl<T> DerefMut for SvmMap<'_, T, true> {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
-        self.0
+        self.mem
     }
 }
 
@@ -116,26 +317,64 @@ fn test_map() {
 
             let context = device.context();
             let queue = context.queue();
-            let mut svm = context.malloc::<u32>(n);
+            let mut svm = context.svm_vec::<u32>(n);
             let mut host = (0..n).map(|i| i as u32).collect::<Vec<_>>();
             queue.memcpy_from_host(&mut svm, &host, None);
             let mut map = queue.map_mut(&mut svm, true);
-            {
-                let mem = unsafe {
-                    from_raw_parts_mut(map.as_mut_ptr().cast::<u32>(), map.len() / size_of::<u32>())
-                };
-                for x in mem {
-                    *x *= 2;
-                }
+            for x in map.iter_mut() {
+                *x *= 2;
             }
-            queue.unmap(map);
+            map.unmap();
             queue.memcpy_to_host(&mut host, &svm, None);
             queue.finish();
 
-            assert!(host
-                .into_iter()
-                .enumerate()
-                .all(|(i, x)| x as usize == 2 * i));
+            assert!(host.iter().enumerate().all(|(i, &x)| x as usize == 2 * i));
+
+            // 提前返回时映射随守卫释放而解除
+            let mut sum = || -> Option<u32> {
+                let map = queue.map(&mut svm);
+                let mut acc = 0u32;
+                for &x in map.iter() {
+                    acc = acc.checked_add(x)?;
+                }
+                Some(acc)
+            };
+            assert!(sum().is_some());
+            queue.memcpy_to_host(&mut host, &svm, None);
+            queue.finish();
+            assert!(host.iter().enumerate().all(|(i, &x)| x as usize == 2 * i));
+
+            // 映射等待用户事件，完成前不能访问数据
+            let gate = context.user_event();
+            let (pending, event) = queue.map_mut_async(&mut svm, true, [(*gate).clone()]);
+            assert!(!pending.is_ready());
+            gate.complete();
+            event.wait();
+            let mut map = pending.wait();
+            map[0] = 7;
+            let unmapped = map.unmap_async([]);
+            queue.memcpy_to_host(
+                &mut host,
+                &svm,
+                Some(&mut EventNode::new([unmapped], false)),
+            );
+            queue.finish();
+            assert_eq!(host[0], 7);
+
+            // 细粒度内存映射时只记录事件，切片按粗粒度映射
+            if device.svm_capabilities().fine_grain_buffer() {
+                let flags = crate::SvmFlags {
+                    fine_grain_buffer: true,
+                    ..Default::default()
+                };
+                let mut svm = context.svm_vec_with::<u32>(n, flags);
+                assert!(svm.is_fine_grain());
+                let mut map = queue.map_mut(&mut svm, false);
+                map.fill(3);
+                map.unmap();
+                let map = queue.map(&mut svm[..1]);
+                assert_eq!(map[0], 3);
+            }
         }
     }
 }
@@ -1,68 +1,203 @@
 ﻿mod capabilities;
 mod map;
+mod vec;
 
 use crate::{
-    bindings::{clSVMAlloc, clSVMFree, CL_MAP_READ, CL_MAP_WRITE, CL_MEM_READ_WRITE},
+    bindings::{cl_command_queue, cl_uint, CL_MAP_READ, CL_MAP_WRITE, CL_MEM_READ_WRITE},
     node::{destruct, NodeParts},
-    AsRaw, CommandQueue, Context, EventNode,
+    AsRaw, CommandQueue, Context, Error, Event, EventNode,
 };
 use std::{
     alloc::Layout,
     ffi::c_void,
-    mem::forget,
+    mem::ManuallyDrop,
     ops::{Deref, DerefMut},
-    ptr::{null, null_mut, NonNull},
+    ptr::{self, null_mut, NonNull},
     slice::{from_raw_parts, from_raw_parts_mut},
 };
 
 pub use capabilities::SvmCapabilities;
-pub use map::SvmMap;
+pub use map::{SvmMap, SvmMapPending, SvmMem};
+pub use vec::{SvmBox, SvmVec};
 
+/// SVM 中的一个 `T`，主机不能直接访问，需要映射或复制。
 #[repr(transparent)]
-pub struct SvmByte(u8);
+pub struct SvmElem<T>(T);
+
+pub type SvmByte = SvmElem<u8>;
+
+/// 可以映射为 `&[T]` 或复制到主机的 SVM 元素类型。
+///
+/// # Safety
+///
+/// SVM 内存分配后未初始化，设备也可能写入任意字节，实现者必须保证任意位模式都是合法的 `T`。
+pub unsafe trait SvmPod: Copy + 'static {}
+
+macro_rules! impl_svm_pod {
+    ($($ty:ty)*) => {
+        $(unsafe impl SvmPod for $ty {})*
+    };
+}
+
+impl_svm_pod!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+
+unsafe impl<T: SvmPod, const N: usize> SvmPod for [T; N] {}
 
 pub struct SvmBlob {
     ctx: Context,
     ptr: NonNull<SvmByte>,
     len: usize,
+    flags: SvmFlags,
 }
 
 unsafe impl Send for SvmBlob {}
 unsafe impl Sync for SvmBlob {}
 
+/// SVM 内存的分配选项，用于 [`Context::malloc_with`]。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct SvmFlags {
+    /// 核函数只读取这块内存。
+    pub read_only: bool,
+    /// 核函数只写入这块内存。
+    pub write_only: bool,
+    /// 分配细粒度缓冲区，主机和设备可以同时访问而不需要映射。
+    pub fine_grain_buffer: bool,
+    /// 支持 SVM 原子操作，隐含 `fine_grain_buffer`。
+    pub atomics: bool,
+}
+
+impl SvmFlags {
+    /// 细粒度内存由主机和设备直接共享，映射和解除映射不需要实际执行。
+    #[inline]
+    pub fn is_fine_grain(&self) -> bool {
+        self.fine_grain_buffer || self.atomics
+    }
+}
+
 impl Context {
+    #[inline]
     pub fn malloc<T: Copy>(&self, len: usize) -> SvmBlob {
+        self.try_malloc::<T>(len).unwrap()
+    }
+
+    /// 分配 SVM 内存，由上下文的所有设备共享。上下文中有不支持 SVM 的设备时返回 [`Error::Unsupported`]。
+    #[inline]
+    pub fn try_malloc<T: Copy>(&self, len: usize) -> Result<SvmBlob, Error> {
+        self.try_malloc_with::<T>(len, SvmFlags::default())
+    }
+
+    #[inline]
+    pub fn malloc_with<T: Copy>(&self, len: usize, flags: SvmFlags) -> SvmBlob {
+        self.try_malloc_with::<T>(len, flags).unwrap()
+    }
+
+    /// 按 `flags` 分配 SVM 内存。上下文中有设备不支持 `flags` 要求的 SVM 能力时返回 [`Error::Unsupported`]，
+    /// 同时设置 `read_only` 和 `write_only` 时返回 [`Error::InvalidValue`]。
+    pub fn try_malloc_with<T: Copy>(&self, len: usize, flags: SvmFlags) -> Result<SvmBlob, Error> {
+        use crate::bindings::{
+            cl_svm_mem_flags, CL_MEM_READ_ONLY, CL_MEM_SVM_ATOMICS, CL_MEM_SVM_FINE_GRAIN_BUFFER,
+            CL_MEM_WRITE_ONLY,
+        };
+
+        let access = match (flags.read_only, flags.write_only) {
+            (false, false) => CL_MEM_READ_WRITE,
+            (true, false) => CL_MEM_READ_ONLY,
+            (false, true) => CL_MEM_WRITE_ONLY,
+            (true, true) => return Err(Error::InvalidValue("clSVMAlloc")),
+        };
+        let fine_grain = flags.is_fine_grain();
+        for device in self.devices() {
+            device.require(2, 0, "clSVMAlloc")?;
+            let capabilities = device.svm_capabilities();
+            if !capabilities.coarse_grain_buffer()
+                || (fine_grain && !capabilities.fine_grain_buffer())
+                || (flags.atomics && !capabilities.atomics())
+            {
+                return Err(Error::Unsupported("clSVMAlloc"));
+            }
+        }
+
+        let raw = [
+            (fine_grain, CL_MEM_SVM_FINE_GRAIN_BUFFER),
+            (flags.atomics, CL_MEM_SVM_ATOMICS),
+        ]
+        .into_iter()
+        .filter(|(enable, _)| *enable)
+        .fold(access as cl_svm_mem_flags, |acc, (_, flag)| {
+            acc | flag as cl_svm_mem_flags
+        });
+
         let layout = Layout::array::<T>(len).unwrap();
         let len = layout.size();
 
-        SvmBlob {
+        Ok(SvmBlob {
             ctx: self.clone(),
             ptr: if len == 0 {
                 NonNull::dangling()
             } else {
-                let ptr = unsafe {
-                    clSVMAlloc(
-                        self.as_raw(),
-                        CL_MEM_READ_WRITE as _,
-                        len,
-                        layout.align() as _,
-                    )
-                };
-                NonNull::new(ptr).unwrap().cast()
+                let ptr =
+                    unsafe { cl_fn!(clSVMAlloc)(self.as_raw(), raw, len, layout.align() as _) };
+                // clSVMAlloc 失败时只返回空指针，不提供错误码
+                NonNull::new(ptr)
+                    .ok_or(Error::MemObjectAllocationFailure("clSVMAlloc"))?
+                    .cast()
             },
             len,
+            flags,
+        })
+    }
+}
+
+/// 释放 SVM 内存后调用的回调，可能在驱动的线程上被调用。
+pub type SvmFreeCallback = dyn FnOnce() + Send + 'static;
+
+struct FreeCallback {
+    ctx: Context,
+    f: Box<SvmFreeCallback>,
+}
+
+extern "C" fn free_callback(
+    _queue: cl_command_queue,
+    num_svm_pointers: cl_uint,
+    svm_pointers: *mut *mut c_void,
+    user_data: *mut c_void,
+) {
+    let FreeCallback { ctx, f } = *unsafe { Box::from_raw(user_data.cast::<FreeCallback>()) };
+    // 提供回调时驱动不会释放内存，需要在回调中释放
+    if num_svm_pointers > 0 {
+        for &ptr in unsafe { from_raw_parts(svm_pointers, num_svm_pointers as _) } {
+            unsafe { cl_fn!(clSVMFree)(ctx.as_raw(), ptr) }
         }
     }
+    f()
+}
+
+/// SVM 迁移的选项，用于 [`CommandQueue::migrate`]。
+#[cfg(cl_2_1)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct MigrateFlags {
+    /// 迁移到主机，否则迁移到队列的设备。
+    pub to_host: bool,
+    /// 不需要保留内容。
+    pub content_undefined: bool,
 }
 
 impl Drop for SvmBlob {
     fn drop(&mut self) {
         if self.len != 0 {
-            unsafe { clSVMFree(self.ctx.as_raw(), self.ptr.as_ptr().cast()) }
+            unsafe { cl_fn!(clSVMFree)(self.ctx.as_raw(), self.ptr.as_ptr().cast()) }
         }
     }
 }
 
+impl SvmBlob {
+    /// 分配时使用的选项。
+    #[inline]
+    pub fn flags(&self) -> SvmFlags {
+        self.flags
+    }
+}
+
 impl AsRaw for SvmBlob {
     type Raw = *mut SvmByte;
     #[inline]
@@ -96,70 +231,239 @@ impl DerefMut for SvmBlob {
     }
 }
 
-#[repr(transparent)]
-pub struct SvmBlobMapped(SvmBlob);
+/// 映射到主机的 [`SvmBlob`]，由 [`CommandQueue::map_blob`] 创建。
+///
+/// 映射期间持有 SVM 内存，因此不能释放或传给核函数。释放时自动解除映射并释放内存。
+pub struct SvmBlobMapped<'a> {
+    queue: &'a CommandQueue,
+    blob: ManuallyDrop<SvmBlob>,
+}
+
+impl Drop for SvmBlobMapped<'_> {
+    fn drop(&mut self) {
+        let blob = unsafe { ManuallyDrop::take(&mut self.blob) };
+        unmap_and_free(self.queue, blob, None)
+    }
+}
+
+impl SvmBlobMapped<'_> {
+    #[inline]
+    pub fn unmap(self) -> SvmBlob {
+        self.unmap_with(None)
+    }
+
+    /// 解除映射并取回 SVM 内存，等待 `event` 中的事件，并记录解除映射的事件。
+    pub fn unmap_with(self, event: Option<&mut EventNode>) -> SvmBlob {
+        let mut this = ManuallyDrop::new(self);
+        let blob = unsafe { ManuallyDrop::take(&mut this.blob) };
+        this.queue
+            .unmap_(
+                blob.ptr.as_ptr().cast(),
+                blob.len,
+                blob.flags.is_fine_grain(),
+                event,
+            )
+            .unwrap();
+        blob
+    }
+
+    /// 解除映射并释放 SVM 内存，`event` 作用于释放命令。
+    pub fn free(self, event: Option<&mut EventNode>) {
+        let mut this = ManuallyDrop::new(self);
+        let blob = unsafe { ManuallyDrop::take(&mut this.blob) };
+        unmap_and_free(this.queue, blob, event)
+    }
+}
+
+fn unmap_and_free(queue: &CommandQueue, blob: SvmBlob, event: Option<&mut EventNode>) {
+    // 乱序队列中释放命令需要显式等待解除映射完成
+    let mut unmapped = EventNode::new([], true);
+    if queue
+        .unmap_(
+            blob.ptr.as_ptr().cast(),
+            blob.len,
+            blob.flags.is_fine_grain(),
+            Some(&mut unmapped),
+        )
+        .is_ok()
+    {
+        if let Some(e) = unmapped.take() {
+            queue.wait(&e)
+        }
+    }
+    // 释放失败时 blob 已被同步释放
+    let _ = queue.try_free(blob, event);
+}
 
-impl AsRaw for SvmBlobMapped {
+impl AsRaw for SvmBlobMapped<'_> {
     type Raw = *mut u8;
     #[inline]
     unsafe fn as_raw(&self) -> Self::Raw {
-        self.0.ptr.as_ptr().cast()
+        self.blob.ptr.as_ptr().cast()
     }
 }
 
-impl Deref for SvmBlobMapped {
+impl Deref for SvmBlobMapped<'_> {
     type Target = [u8];
     #[inline]
     fn deref(&self) -> &Self::Target {
-        let len = self.0.len;
+        let len = self.blob.len;
         if len == 0 {
             &[]
         } else {
-            unsafe { from_raw_parts(self.0.ptr.as_ptr().cast(), len) }
+            unsafe { from_raw_parts(self.blob.ptr.as_ptr().cast(), len) }
         }
     }
 }
 
-impl DerefMut for SvmBlobMapped {
+impl DerefMut for SvmBlobMapped<'_> {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
-        let len = self.0.len;
+        let len = self.blob.len;
         if len == 0 {
             &mut []
         } else {
-            unsafe { from_raw_parts_mut(self.0.ptr.as_ptr().cast(), len) }
+            unsafe { from_raw_parts_mut(self.blob.ptr.as_ptr().cast(), len) }
         }
     }
 }
 
 impl CommandQueue {
-    pub fn map_blob(&self, mut blob: SvmBlob) -> SvmBlobMapped {
+    #[inline]
+    pub fn map_blob(&self, blob: SvmBlob) -> SvmBlobMapped<'_> {
+        self.map_blob_async(blob, []).0.wait()
+    }
+
+    /// 在 `wait` 中的事件完成后映射 `blob`，不阻塞队列，返回尚未完成的映射和映射事件。
+    pub fn map_blob_async(
+        &self,
+        mut blob: SvmBlob,
+        wait: impl IntoIterator<Item = Event>,
+    ) -> (SvmMapPending<'_, SvmBlobMapped<'_>>, Event) {
+        let mut node = EventNode::new(wait, true);
         self.map_(
             blob.as_mut_ptr().cast(),
             blob.len(),
             CL_MAP_READ | CL_MAP_WRITE,
-            None,
+            blob.flags.is_fine_grain(),
+            Some(&mut node),
         );
-        self.finish();
-        SvmBlobMapped(blob)
+        let event = node.take().unwrap();
+        let mapped = SvmBlobMapped {
+            queue: self,
+            blob: ManuallyDrop::new(blob),
+        };
+        (SvmMapPending::new(self, mapped, event.clone()), event)
     }
 
-    pub fn unmap_blob(&self, mut blob: SvmBlobMapped) -> SvmBlob {
-        if !self.fine_grain_svm() {
-            cl!(clEnqueueSVMUnmap(
-                self.as_raw(),
-                blob.0.as_mut_ptr().cast(),
-                0,
-                null(),
+    #[inline]
+    pub fn free(&self, blob: SvmBlob, event: Option<&mut EventNode>) {
+        self.try_free(blob, event).unwrap()
+    }
+
+    #[inline]
+    pub fn try_free(&self, blob: SvmBlob, event: Option<&mut EventNode>) -> Result<(), Error> {
+        self.try_free_all([blob], None, event)
+    }
+
+    #[inline]
+    pub fn free_all(
+        &self,
+        blobs: impl IntoIterator<Item = SvmBlob>,
+        callback: Option<Box<SvmFreeCallback>>,
+        event: Option<&mut EventNode>,
+    ) {
+        self.try_free_all(blobs, callback, event).unwrap()
+    }
+
+    /// 用一个命令释放多个 SVM 内存，释放完成后调用 `callback`。
+    ///
+    /// 有内存不属于队列的上下文时返回 [`Error::InvalidContext`]。失败时 `blobs` 会被立即释放。
+    pub fn try_free_all(
+        &self,
+        blobs: impl IntoIterator<Item = SvmBlob>,
+        callback: Option<Box<SvmFreeCallback>>,
+        event: Option<&mut EventNode>,
+    ) -> Result<(), Error> {
+        let ctx = self.ctx();
+        let blobs = blobs.into_iter().collect::<Vec<_>>();
+        if blobs
+            .iter()
+            .any(|blob| unsafe { blob.ctx.as_raw() != ctx.as_raw() })
+        {
+            return Err(Error::InvalidContext("clEnqueueSVMFree"));
+        }
+        let mut ptrs = blobs
+            .iter()
+            .filter(|blob| blob.len != 0)
+            .map(|blob| blob.ptr.as_ptr().cast::<c_void>())
+            .collect::<Vec<_>>();
+        let user_data = callback.map(|f| Box::into_raw(Box::new(FreeCallback { ctx, f })));
+
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        let ans = try_cl!(clEnqueueSVMFree(
+            self.as_raw(),
+            ptrs.len() as _,
+            if ptrs.is_empty() {
                 null_mut()
-            ))
+            } else {
+                ptrs.as_mut_ptr()
+            },
+            if user_data.is_some() {
+                Some(free_callback)
+            } else {
+                None
+            },
+            user_data.map_or(null_mut(), |p| p.cast()),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ));
+        match ans {
+            Ok(()) => {
+                // 内存由队列释放，只释放各自持有的上下文
+                for blob in blobs {
+                    let blob = ManuallyDrop::new(blob);
+                    drop(unsafe { ptr::read(&blob.ctx) })
+                }
+                Ok(())
+            }
+            Err(e) => {
+                if let Some(p) = user_data {
+                    drop(unsafe { Box::from_raw(p) })
+                }
+                Err(e)
+            }
         }
-        blob.0
     }
 
-    pub fn free(&self, blob: SvmBlob, event: Option<&mut EventNode>) {
-        let mut ptr = blob.ptr.as_ptr().cast();
-        forget(blob);
+    /// 用 `pattern` 填充 `dst`，参数不合法或入队失败时 panic，见 [`CommandQueue::try_fill`]。
+    #[inline]
+    pub fn fill<T: Copy>(&self, dst: &mut [SvmElem<T>], pattern: T, event: Option<&mut EventNode>) {
+        self.try_fill(dst, pattern, event).unwrap()
+    }
+
+    /// 用 `pattern` 填充 `dst`。`T` 的大小必须是不超过 128 字节的 2 的幂。
+    ///
+    /// `dst` 的地址或长度不是 `T` 大小的整数倍时返回 [`Error::InvalidValue`]。
+    pub fn try_fill<T: Copy>(
+        &self,
+        dst: &mut [SvmElem<T>],
+        pattern: T,
+        event: Option<&mut EventNode>,
+    ) -> Result<(), Error> {
+        const { assert!(size_of::<T>().is_power_of_two() && size_of::<T>() <= 128) };
+        // 对齐小于大小的类型（如 `[u8; 4]`）不保证满足 OpenCL™ 的要求
+        if !dst.is_empty()
+            && !(dst.as_ptr() as usize | size_of_val(dst)).is_multiple_of(size_of::<T>())
+        {
+            return Err(Error::InvalidValue("CommandQueue::try_fill"));
+        }
 
         let NodeParts {
             num_events_in_wait_list,
@@ -167,24 +471,102 @@ impl CommandQueue {
             event,
             ..
         } = destruct(event);
-        cl!(clEnqueueSVMFree(
+        if dst.is_empty() {
+            self.barrier_(num_events_in_wait_list, event_wait_list, event);
+            return Ok(());
+        }
+        try_cl!(clEnqueueSVMMemFill(
+            self.as_raw(),
+            dst.as_mut_ptr().cast(),
+            (&raw const pattern).cast(),
+            size_of::<T>(),
+            size_of_val(dst),
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+        ))
+    }
+
+    /// 将 `mem` 迁移到队列的设备或主机。
+    #[cfg(cl_2_1)]
+    #[inline]
+    pub fn migrate<T: Copy>(
+        &self,
+        mem: &[SvmElem<T>],
+        flags: MigrateFlags,
+        event: Option<&mut EventNode>,
+    ) {
+        self.try_migrate(mem, flags, event).unwrap()
+    }
+
+    /// 将 `mem` 迁移到队列的设备或主机。设备不支持 OpenCL™ 2.1 时返回 [`Error::Unsupported`]。
+    #[cfg(cl_2_1)]
+    pub fn try_migrate<T: Copy>(
+        &self,
+        mem: &[SvmElem<T>],
+        flags: MigrateFlags,
+        event: Option<&mut EventNode>,
+    ) -> Result<(), Error> {
+        use crate::bindings::{
+            cl_mem_migration_flags, CL_MIGRATE_MEM_OBJECT_CONTENT_UNDEFINED,
+            CL_MIGRATE_MEM_OBJECT_HOST,
+        };
+
+        self.device().require(2, 1, "clEnqueueSVMMigrateMem")?;
+        let flags = [
+            (flags.to_host, CL_MIGRATE_MEM_OBJECT_HOST),
+            (
+                flags.content_undefined,
+                CL_MIGRATE_MEM_OBJECT_CONTENT_UNDEFINED,
+            ),
+        ]
+        .into_iter()
+        .filter(|(enable, _)| *enable)
+        .fold(0, |acc, (_, flag)| acc | flag as cl_mem_migration_flags);
+
+        let NodeParts {
+            num_events_in_wait_list,
+            event_wait_list,
+            event,
+            ..
+        } = destruct(event);
+        if mem.is_empty() {
+            self.barrier_(num_events_in_wait_list, event_wait_list, event);
+            return Ok(());
+        }
+        let mut ptr = mem.as_ptr().cast::<c_void>();
+        let size = size_of_val(mem);
+        try_cl!(clEnqueueSVMMigrateMem(
             self.as_raw(),
             1,
             &mut ptr,
-            None,
-            null_mut(),
+            &size,
+            flags,
             num_events_in_wait_list,
             event_wait_list,
             event,
         ))
     }
 
-    pub fn free_mapped(&self, blob: SvmBlobMapped, event: Option<&mut EventNode>) {
-        self.free(blob.0, event)
+    #[inline]
+    pub fn memcpy<T: Copy>(
+        &self,
+        dst: &mut [SvmElem<T>],
+        src: &[SvmElem<T>],
+        event: Option<&mut EventNode>,
+    ) {
+        self.try_memcpy(dst, src, event).unwrap()
     }
 
-    pub fn memcpy(&self, dst: &mut [SvmByte], src: &[SvmByte], event: Option<&mut EventNode>) {
-        assert_eq!(size_of_val(dst), size_of_val(src));
+    pub fn try_memcpy<T: Copy>(
+        &self,
+        dst: &mut [SvmElem<T>],
+        src: &[SvmElem<T>],
+        event: Option<&mut EventNode>,
+    ) -> Result<(), Error> {
+        if dst.len() != src.len() {
+            return Err(Error::InvalidValue("clEnqueueSVMMemcpy"));
+        }
         self.memcpy_any(
             dst.as_mut_ptr().cast(),
             src.as_ptr().cast(),
@@ -193,13 +575,25 @@ impl CommandQueue {
         )
     }
 
+    #[inline]
     pub fn memcpy_from_host<T: Copy>(
         &self,
-        dst: &mut [SvmByte],
+        dst: &mut [SvmElem<T>],
         src: &[T],
         event: Option<&mut EventNode>,
     ) {
-        assert_eq!(size_of_val(dst), size_of_val(src));
+        self.try_memcpy_from_host(dst, src, event).unwrap()
+    }
+
+    pub fn try_memcpy_from_host<T: Copy>(
+        &self,
+        dst: &mut [SvmElem<T>],
+        src: &[T],
+        event: Option<&mut EventNode>,
+    ) -> Result<(), Error> {
+        if dst.len() != src.len() {
+            return Err(Error::InvalidValue("clEnqueueSVMMemcpy"));
+        }
         self.memcpy_any(
             dst.as_mut_ptr().cast(),
             src.as_ptr().cast(),
@@ -208,13 +602,25 @@ impl CommandQueue {
         )
     }
 
-    pub fn memcpy_to_host<T: Copy>(
+    #[inline]
+    pub fn memcpy_to_host<T: SvmPod>(
         &self,
         dst: &mut [T],
-        src: &[SvmByte],
+        src: &[SvmElem<T>],
         event: Option<&mut EventNode>,
     ) {
-        assert_eq!(size_of_val(dst), size_of_val(src));
+        self.try_memcpy_to_host(dst, src, event).unwrap()
+    }
+
+    pub fn try_memcpy_to_host<T: SvmPod>(
+        &self,
+        dst: &mut [T],
+        src: &[SvmElem<T>],
+        event: Option<&mut EventNode>,
+    ) -> Result<(), Error> {
+        if dst.len() != src.len() {
+            return Err(Error::InvalidValue("clEnqueueSVMMemcpy"));
+        }
         self.memcpy_any(
             dst.as_mut_ptr().cast(),
             src.as_ptr().cast(),
@@ -229,14 +635,14 @@ impl CommandQueue {
         src: *const c_void,
         len: usize,
         event: Option<&mut EventNode>,
-    ) {
+    ) -> Result<(), Error> {
         let NodeParts {
             num_events_in_wait_list,
             event_wait_list,
             event,
             ..
         } = destruct(event);
-        cl!(clEnqueueSVMMemcpy(
+        try_cl!(clEnqueueSVMMemcpy(
             self.as_raw(),
             CL_FALSE,
             dst,
@@ -264,3 +670,167 @@ fn test() {
         }
     }
 }
+
+#[test]
+fn test_map_blob() {
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            if !device.svm_capabilities().coarse_grain_buffer() {
+                continue;
+            }
+
+            let ctx = device.context();
+            let queue = ctx.queue();
+            let mut mapped = queue.map_blob(ctx.malloc::<u8>(64));
+            mapped.fill(1);
+            let blob = mapped.unmap();
+            // 释放映射时解除映射并释放内存
+            let mut mapped = queue.map_blob(blob);
+            assert!(mapped.iter().all(|&x| x == 1));
+            mapped[0] = 0;
+            drop(mapped);
+            queue.finish();
+        }
+    }
+}
+
+#[test]
+fn test_fill() {
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            if !device.svm_capabilities().coarse_grain_buffer() {
+                continue;
+            }
+
+            let ctx = device.context();
+            let queue = ctx.queue();
+            let mut vec = ctx.svm_vec::<u32>(256);
+            queue.fill(&mut vec, 0xdead_beef, None);
+            queue.fill(&mut vec[..0], 0, None);
+            let mut host = vec![0; 256];
+            queue.memcpy_to_host(&mut host, &vec, None);
+            queue.finish();
+            assert!(host.iter().all(|&x| x == 0xdead_beef));
+        }
+    }
+}
+
+#[cfg(cl_2_1)]
+#[test]
+fn test_migrate() {
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            if !device.svm_capabilities().coarse_grain_buffer() || device.require(2, 1, "").is_err()
+            {
+                continue;
+            }
+
+            let ctx = device.context();
+            let queue = ctx.queue();
+            let mut vec = ctx.svm_vec::<u32>(256);
+            queue.fill(&mut vec, 7, None);
+            queue.migrate(&vec, MigrateFlags::default(), None);
+            let mut host = vec![0; 256];
+            queue.memcpy_to_host(&mut host, &vec, None);
+            queue.finish();
+            assert!(host.iter().all(|&x| x == 7));
+        }
+    }
+}
+
+#[test]
+fn test_free_all() {
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            if !device.svm_capabilities().coarse_grain_buffer() {
+                continue;
+            }
+
+            let ctx = device.context();
+            let queue = ctx.queue();
+            let (tx, rx) = std::sync::mpsc::channel();
+            let blobs = [64, 0, 128].map(|n| ctx.malloc::<u8>(n));
+            queue.free_all(blobs, Some(Box::new(move || tx.send(()).unwrap())), None);
+            queue.finish();
+            rx.recv().unwrap();
+
+            // 其他上下文的内存不能由这个队列释放
+            let other = device.context();
+            assert_eq!(
+                queue.try_free(other.malloc::<u8>(64), None),
+                Err(Error::InvalidContext("clEnqueueSVMFree"))
+            );
+            let blobs = [ctx.malloc::<u8>(64), other.malloc::<u8>(64)];
+            assert!(matches!(
+                queue.try_free_all(blobs, None, None),
+                Err(Error::InvalidContext("clEnqueueSVMFree"))
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_flags() {
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let capabilities = device.svm_capabilities();
+            if !capabilities.coarse_grain_buffer() {
+                continue;
+            }
+
+            let ctx = device.context();
+            let both = SvmFlags {
+                read_only: true,
+                write_only: true,
+                ..Default::default()
+            };
+            assert!(matches!(
+                ctx.try_malloc_with::<u32>(256, both),
+                Err(Error::InvalidValue("clSVMAlloc"))
+            ));
+
+            if capabilities.fine_grain_buffer() {
+                let flags = SvmFlags {
+                    fine_grain_buffer: true,
+                    atomics: capabilities.atomics(),
+                    ..Default::default()
+                };
+                let blob = ctx.malloc_with::<u32>(256, flags);
+                assert_eq!(blob.flags(), flags);
+                let queue = ctx.queue();
+                let mut mapped = queue.map_blob(blob);
+                mapped.fill(1);
+                let _ = mapped.unmap();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_set_arg_host() {
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            let ctx = device.context();
+            let queue = ctx.queue();
+            let program = ctx
+                .build_from_source(
+                    "kernel void twice(global uint* x) { x[get_global_id(0)] *= 2; }",
+                    c"",
+                )
+                .unwrap();
+            let mut kernel = program.get_kernel(c"twice").unwrap();
+            let mut host = (0..64u32).collect::<Vec<_>>();
+            if device.svm_capabilities().fine_grain_system() {
+                unsafe { kernel.set_arg_host(0, &mut host) };
+                kernel.launch(&[0], &[host.len()], &[1], &queue, None);
+                queue.finish();
+                assert!(host.iter().enumerate().all(|(i, &x)| x as usize == 2 * i));
+            } else {
+                assert!(matches!(
+                    unsafe { kernel.try_set_arg_host(0, &mut host) },
+                    Err(Error::Unsupported("clSetKernelArgSVMPointer"))
+                ));
+            }
+        }
+    }
+}
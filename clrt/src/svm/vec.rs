@@ -0,0 +1,183 @@
+use super::{SvmBlob, SvmElem, SvmFlags, SvmPod};
+use crate::{kernel::accepts_global_pointer, ArgInfo, Argument, Context, Error, Kernel};
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    slice::{from_raw_parts, from_raw_parts_mut},
+};
+
+/// 保留元素类型和长度的 SVM 数组，由 [`Context::svm_vec`] 创建。
+pub struct SvmVec<T> {
+    blob: SvmBlob,
+    len: usize,
+    _phantom: PhantomData<T>,
+}
+
+/// SVM 中的单个 `T`，由 [`Context::svm_box`] 创建。
+#[repr(transparent)]
+pub struct SvmBox<T>(SvmVec<T>);
+
+impl Context {
+    #[inline]
+    pub fn svm_vec<T: SvmPod>(&self, len: usize) -> SvmVec<T> {
+        self.try_svm_vec(len).unwrap()
+    }
+
+    /// 分配能容纳 `len` 个 `T` 的 SVM 内存，内容未初始化。
+    #[inline]
+    pub fn try_svm_vec<T: SvmPod>(&self, len: usize) -> Result<SvmVec<T>, Error> {
+        self.try_svm_vec_with(len, SvmFlags::default())
+    }
+
+    #[inline]
+    pub fn svm_vec_with<T: SvmPod>(&self, len: usize, flags: SvmFlags) -> SvmVec<T> {
+        self.try_svm_vec_with(len, flags).unwrap()
+    }
+
+    /// 按 `flags` 分配能容纳 `len` 个 `T` 的 SVM 内存，内容未初始化。
+    pub fn try_svm_vec_with<T: SvmPod>(
+        &self,
+        len: usize,
+        flags: SvmFlags,
+    ) -> Result<SvmVec<T>, Error> {
+        Ok(SvmVec {
+            blob: self.try_malloc_with::<T>(len, flags)?,
+            len,
+            _phantom: PhantomData,
+        })
+    }
+
+    #[inline]
+    pub fn svm_box<T: SvmPod>(&self) -> SvmBox<T> {
+        self.try_svm_box().unwrap()
+    }
+
+    /// 分配能容纳一个 `T` 的 SVM 内存，内容未初始化。
+    #[inline]
+    pub fn try_svm_box<T: SvmPod>(&self) -> Result<SvmBox<T>, Error> {
+        self.try_svm_vec(1).map(SvmBox)
+    }
+}
+
+impl<T> SvmVec<T> {
+    #[inline]
+    fn ptr(&self) -> NonNull<SvmElem<T>> {
+        // 零字节的分配使用按字节对齐的悬垂指针，不能转换为 `T` 的指针
+        if self.blob.len == 0 {
+            NonNull::dangling()
+        } else {
+            self.blob.ptr.cast()
+        }
+    }
+
+    #[inline]
+    pub fn ctx(&self) -> &Context {
+        &self.blob.ctx
+    }
+
+    /// 分配时使用的选项。
+    #[inline]
+    pub fn flags(&self) -> SvmFlags {
+        self.blob.flags()
+    }
+}
+
+impl<T> Deref for SvmVec<T> {
+    type Target = [SvmElem<T>];
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { from_raw_parts(self.ptr().as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for SvmVec<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { from_raw_parts_mut(self.ptr().as_ptr(), self.len) }
+    }
+}
+
+impl<T> SvmBox<T> {
+    /// 分配时使用的选项。
+    #[inline]
+    pub fn flags(&self) -> SvmFlags {
+        self.0.flags()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[SvmElem<T>] {
+        &self.0
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [SvmElem<T>] {
+        &mut self.0
+    }
+}
+
+impl<T> Deref for SvmBox<T> {
+    type Target = SvmElem<T>;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0[0]
+    }
+}
+
+impl<T> DerefMut for SvmBox<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0[0]
+    }
+}
+
+impl<T> Argument for SvmVec<T> {
+    #[inline]
+    fn try_set_to(&self, kernel: &mut Kernel, index: usize) -> Result<(), Error> {
+        self.as_ptr().try_set_to(kernel, index)
+    }
+
+    #[inline]
+    fn accepts(&self, info: &ArgInfo) -> bool {
+        accepts_global_pointer(info)
+    }
+}
+
+impl<T> Argument for SvmBox<T> {
+    #[inline]
+    fn try_set_to(&self, kernel: &mut Kernel, index: usize) -> Result<(), Error> {
+        self.0.try_set_to(kernel, index)
+    }
+
+    #[inline]
+    fn accepts(&self, info: &ArgInfo) -> bool {
+        accepts_global_pointer(info)
+    }
+}
+
+#[test]
+fn test() {
+    for platform in crate::Platform::all() {
+        for device in platform.devices() {
+            if !device.svm_capabilities().coarse_grain_buffer() {
+                continue;
+            }
+
+            let ctx = device.context();
+            let queue = ctx.queue();
+            let vec = ctx.svm_vec::<f32>(0);
+            assert!(vec.is_empty());
+
+            let mut x = ctx.svm_box::<u64>();
+            queue.memcpy_from_host(x.as_mut_slice(), &[42], None);
+            assert_eq!(
+                queue.try_memcpy_from_host(x.as_mut_slice(), &[1, 2], None),
+                Err(Error::InvalidValue("clEnqueueSVMMemcpy"))
+            );
+            let mut host = [0u64];
+            queue.memcpy_to_host(&mut host, x.as_slice(), None);
+            queue.finish();
+            assert_eq!(host, [42]);
+        }
+    }
+}
@@ -1,2 +1,4 @@
-﻿#define CL_TARGET_OPENCL_VERSION 200
+﻿#ifndef CL_TARGET_OPENCL_VERSION
+#define CL_TARGET_OPENCL_VERSION 210
+#endif
 #include <CL/opencl.h>
@@ -1,20 +1,178 @@
 #![deny(warnings)]
 
-use std::path::PathBuf;
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 pub struct OpenclPath {
     pub inc: PathBuf,
     pub lib: PathBuf,
+    /// 头文件声明的最高 OpenCL™ 版本。
+    pub version: ClVersion,
 }
 
-pub fn find_opencl() -> Option<OpenclPath> {
-    fn env_path(key: &str) -> Option<PathBuf> {
-        println!("cargo:rerun-if-env-changed={key}");
-        std::env::var(key).ok().map(PathBuf::from)
+/// OpenCL™ 版本号。
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct ClVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl ClVersion {
+    pub const ALL: [Self; 7] = [
+        Self::new(1, 0),
+        Self::new(1, 1),
+        Self::new(1, 2),
+        Self::new(2, 0),
+        Self::new(2, 1),
+        Self::new(2, 2),
+        Self::new(3, 0),
+    ];
+
+    #[inline]
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
     }
 
+    /// 解析 `CL_TARGET_OPENCL_VERSION` 的格式，如 `300`。
+    pub fn from_target(target: &str) -> Option<Self> {
+        let target = target.trim().parse::<u32>().ok()?;
+        let ans = Self::new(target / 100, target % 100 / 10);
+        Some(ans).filter(|v| Self::ALL.contains(v) && v.target() == target)
+    }
+
+    /// 转换为 `CL_TARGET_OPENCL_VERSION` 的格式。
+    #[inline]
+    pub const fn target(self) -> u32 {
+        self.major * 100 + self.minor * 10
+    }
+}
+
+impl fmt::Display for ClVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+pub fn find_opencl() -> Option<OpenclPath> {
+    let inc = find_opencl_headers()?;
     Some(OpenclPath {
-        inc: env_path("OPENCL_HEADERS")?,
-        lib: env_path("OPENCL_LIB")?,
+        version: header_version(&inc)?,
+        lib: find_opencl_lib()?,
+        inc,
     })
 }
+
+/// 查找包含 `CL/cl.h` 的目录。
+///
+/// 依次尝试环境变量 `OPENCL_HEADERS`、`pkg-config OpenCL` 和常见的安装位置。
+pub fn find_opencl_headers() -> Option<PathBuf> {
+    const PREFIXES: &[&str] = &[
+        "/usr/include",
+        "/usr/local/include",
+        "/opt/rocm/include",
+        "/usr/local/cuda/include",
+        "/opt/cuda/include",
+        "/opt/pocl/include",
+        "/usr/local/pocl/include",
+    ];
+
+    let found = |dir: &Path| dir.join("CL").join("cl.h").is_file();
+    if let Some(dir) = env_path("OPENCL_HEADERS") {
+        return Some(dir);
+    }
+    pkg_config("--cflags-only-I", "-I")
+        .into_iter()
+        .chain(PREFIXES.iter().map(PathBuf::from))
+        .find(|dir| found(dir))
+}
+
+/// 查找包含 OpenCL™ ICD 加载器的目录。
+///
+/// 依次尝试环境变量 `OPENCL_LIB`、`pkg-config OpenCL` 和常见的安装位置。
+pub fn find_opencl_lib() -> Option<PathBuf> {
+    const PREFIXES: &[&str] = &[
+        "/usr/lib/x86_64-linux-gnu",
+        "/usr/lib/aarch64-linux-gnu",
+        "/usr/lib64",
+        "/usr/lib",
+        "/usr/local/lib",
+        "/opt/rocm/lib",
+        "/usr/local/cuda/lib64",
+        "/opt/cuda/lib64",
+        "/opt/pocl/lib",
+        "/usr/local/pocl/lib",
+    ];
+    const NAMES: &[&str] = &["libOpenCL.so", "libOpenCL.dylib", "OpenCL.lib"];
+
+    let found = |dir: &Path| NAMES.iter().any(|name| dir.join(name).is_file());
+    if let Some(dir) = env_path("OPENCL_LIB") {
+        return Some(dir);
+    }
+    pkg_config("--libs-only-L", "-L")
+        .into_iter()
+        .chain(PREFIXES.iter().map(PathBuf::from))
+        .find(|dir| found(dir))
+}
+
+/// 读取 `inc` 中的头文件声明的最高 OpenCL™ 版本。
+pub fn header_version(inc: &Path) -> Option<ClVersion> {
+    ["cl_version.h", "cl.h"]
+        .iter()
+        .filter_map(|name| fs::read_to_string(inc.join("CL").join(name)).ok())
+        .flat_map(|text| {
+            text.lines()
+                .filter_map(|line| {
+                    let line = line.trim_start().strip_prefix('#')?.trim_start();
+                    let name = line.strip_prefix("define")?.split_whitespace().next()?;
+                    let (major, minor) = name.strip_prefix("CL_VERSION_")?.split_once('_')?;
+                    Some(ClVersion::new(major.parse().ok()?, minor.parse().ok()?))
+                })
+                .collect::<Vec<_>>()
+        })
+        .max()
+}
+
+/// 读取环境变量 `CL_TARGET_OPENCL_VERSION` 指定的目标版本。
+pub fn target_version() -> Option<ClVersion> {
+    println!("cargo:rerun-if-env-changed=CL_TARGET_OPENCL_VERSION");
+    let target = env::var("CL_TARGET_OPENCL_VERSION").ok()?;
+    Some(ClVersion::from_target(&target).unwrap_or_else(|| {
+        panic!("CL_TARGET_OPENCL_VERSION={target} is not a valid OpenCL version")
+    }))
+}
+
+fn env_path(key: &str) -> Option<PathBuf> {
+    println!("cargo:rerun-if-env-changed={key}");
+    env::var(key).ok().map(PathBuf::from)
+}
+
+fn pkg_config(arg: &str, prefix: &str) -> Vec<PathBuf> {
+    println!("cargo:rerun-if-env-changed=PKG_CONFIG_PATH");
+    let Ok(output) = Command::new("pkg-config").args([arg, "OpenCL"]).output() else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .filter_map(|flag| flag.strip_prefix(prefix))
+        .map(PathBuf::from)
+        .collect()
+}
+
+#[test]
+fn test_version() {
+    assert_eq!(ClVersion::from_target("300"), Some(ClVersion::new(3, 0)));
+    assert_eq!(ClVersion::from_target("120"), Some(ClVersion::new(1, 2)));
+    assert_eq!(ClVersion::from_target("121"), None);
+    assert_eq!(ClVersion::from_target("230"), None);
+    assert_eq!(ClVersion::new(2, 1).target(), 210);
+    assert!(ClVersion::new(1, 2) < ClVersion::new(2, 0));
+
+    let inc = Path::new(env!("CARGO_MANIFEST_DIR")).join("../clrt/include");
+    assert_eq!(header_version(&inc), Some(ClVersion::new(2, 2)));
+}